@@ -0,0 +1,78 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+// O crate só tem o binário, então o módulo do lexer é incluído diretamente
+#[allow(dead_code, unused_imports)]
+#[path = "../src/lexer/mod.rs"]
+mod lexer;
+
+use lexer::{LogosLexer, TokenType};
+
+
+
+// Trecho representativo de código Odin, repetido para formar um arquivo grande
+const SAMPLE: &str = r#"
+# Cálculo de estatísticas simples
+func media(valores: list) -> float:
+    total = 0.0
+    for valor in valores:
+        total += valor
+    return total / len(valores)
+
+class Contador:
+    func __init__(self, inicio = 0):
+        self.valor = inicio
+
+    func incrementa(self, passo = 1):
+        self.valor += passo
+        return self.valor
+
+dados = [1, 2, 3, 0x1F, 0b1010, 1_000_000, 3.14, 2.5e-3, 1j]
+nome = 'Odin'
+mensagem = f"Olá, {nome}! Média: {media(dados):.2f}"
+caminho = r"C:\temp\arquivo.txt"
+texto = """Um texto
+com várias linhas"""
+
+if len(dados) >= 3 and not vazio:
+    resultado = (dados[0] << 2) | (dados[1] & 0xFF) ^ ~dados[2]
+elif dados[0] != 0:
+    resultado = dados[0] // 2 ** 3 % 7
+else:
+    resultado = None
+"#;
+
+fn large_source() -> String {
+    SAMPLE.repeat(1_000)
+}
+
+fn count_owned(source: &str) -> usize {
+    let mut lexer = LogosLexer::new(source);
+    let mut count = 0;
+    while lexer.next_token_recovering().token_type != TokenType::Eof {
+        count += 1;
+    }
+    count
+}
+
+fn count_borrowed(source: &str) -> usize {
+    let mut lexer = LogosLexer::new(source);
+    let mut count = 0;
+    while lexer.next_borrowed_recovering().token_type != TokenType::Eof {
+        count += 1;
+    }
+    count
+}
+
+fn bench_lexers(c: &mut Criterion) {
+    let source = large_source();
+    assert_eq!(count_owned(&source), count_borrowed(&source));
+
+    let mut group = c.benchmark_group("lexer");
+    group.throughput(Throughput::Bytes(source.len() as u64));
+    group.bench_function("owned", |b| b.iter(|| count_owned(black_box(&source))));
+    group.bench_function("borrowed", |b| b.iter(|| count_borrowed(black_box(&source))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_lexers);
+criterion_main!(benches);
@@ -0,0 +1,82 @@
+use std::fmt;
+use super::token::Span;
+
+
+
+/// Categoria de um erro encontrado durante a análise léxica
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum DiagnosticCode {
+    InconsistentDedent,   // desindentação que não volta a nenhum nível anterior
+    TabError,             // mistura ambígua de tabs e espaços
+    InvalidContinuation,  // conteúdo após '\' de continuação de linha
+    UnterminatedString,
+    InvalidEscape,
+    NonAsciiBytes,        // caractere não ASCII em literal de bytes
+    InvalidNumber,
+    InvalidFString,
+    MixedScriptIdentifier, // identificador que mistura sistemas de escrita, como latino e cirílico
+    ConfusableIdentifier,  // identificador visualmente igual a outro já usado
+}
+
+impl DiagnosticCode {
+    /// Código estável usado por editores e ferramentas para identificar o erro
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticCode::InconsistentDedent => "L0001",
+            DiagnosticCode::TabError => "L0002",
+            DiagnosticCode::InvalidContinuation => "L0003",
+            DiagnosticCode::UnterminatedString => "L0004",
+            DiagnosticCode::InvalidEscape => "L0005",
+            DiagnosticCode::NonAsciiBytes => "L0006",
+            DiagnosticCode::InvalidNumber => "L0007",
+            DiagnosticCode::InvalidFString => "L0008",
+            DiagnosticCode::MixedScriptIdentifier => "L0009",
+            DiagnosticCode::ConfusableIdentifier => "L0010",
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Gravidade de um diagnóstico. Avisos não impedem a execução do código
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Erro ou aviso léxico com o trecho do código-fonte onde ocorreu
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+    pub code: DiagnosticCode,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+}
+
+impl Diagnostic {
+    pub fn new(code: DiagnosticCode, message: String, span: Span) -> Self {
+        Diagnostic { code, severity: Severity::Error, message, span }
+    }
+    
+    pub fn warning(code: DiagnosticCode, message: String, span: Span) -> Self {
+        Diagnostic { code, severity: Severity::Warning, message, span }
+    }
+    
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.severity {
+            Severity::Error => write!(f, "[{}] {}", self.code, self.message),
+            Severity::Warning => write!(f, "[{}] aviso: {}", self.code, self.message),
+        }
+    }
+}
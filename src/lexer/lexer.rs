@@ -1,365 +1,1568 @@
-use super::token::{Token, TokenType, lookup_identifier};
+use std::borrow::Cow;
+use super::diagnostic::{Diagnostic, DiagnosticCode};
+use super::logos_lexer::LogosLexer;
+use super::token::{BorrowedToken, Span, TokenType, lookup_identifier};
+use super::unicode::{is_identifier_continue, is_identifier_start, normalize_identifier};
 
 
 
-pub struct Lexer {
-    input: Vec<char>,
-    position: usize,      // posição atual no input (aponta para o char atual)
-    read_position: usize, // posição de leitura atual (aponta para o próximo char)
-    ch: char,             // caractere atual sendo examinado
-    line: usize,          // linha atual
-    column: usize,        // coluna atual
-    indent_levels: Vec<usize>, // pilha de níveis de indentação
-    current_indent: usize,     // nível de indentação atual
-    pending_tokens: Vec<Token>, // tokens pendentes (usado para indentação/desindentação)
+/// Define como tabs são interpretados na indentação
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TabPolicy {
+    /// Rejeita indentações cujo significado dependa da largura do tab,
+    /// como o `TabError` do Python
+    #[default]
+    Strict,
+    /// Cada tab avança até a próxima coluna múltipla da largura informada
+    Width(usize),
 }
 
-impl Lexer {
-    pub fn new(input: &str) -> Self {
-        let mut lexer = Lexer {
-            input: input.chars().collect(),
-            position: 0,
-            read_position: 0,
-            ch: '\0', // valor inicial - será atualizado por read_char()
-            line: 1,
-            column: 0,
-            indent_levels: vec![0], // começamos no nível de indentação 0
-            current_indent: 0,
-            pending_tokens: Vec::new(),
+impl TabPolicy {
+    // Mede a indentação formada pelos espaços e tabs iniciais de `whitespace`. Retorna a
+    // contagem principal e a alternativa, que trata cada tab como 1 coluna e serve para
+    // detectar mistura de tabs e espaços
+    pub(super) fn measure(self, whitespace: impl Iterator<Item = char>) -> (usize, usize) {
+        let tab_width = match self {
+            TabPolicy::Strict => 8,
+            TabPolicy::Width(width) => width.max(1),
         };
         
-        lexer.read_char();
-        lexer
+        let mut indent = 0;
+        let mut alt_indent = 0;
+        for ch in whitespace {
+            match ch {
+                '\t' => indent = (indent / tab_width + 1) * tab_width,
+                ' ' => indent += 1,
+                _ => break,
+            }
+            alt_indent += 1;
+        }
+        
+        match self {
+            TabPolicy::Strict => (indent, alt_indent),
+            // Com largura fixa, apenas a contagem principal é considerada
+            TabPolicy::Width(_) => (indent, indent),
+        }
+    }
+}
+
+// Pilha de níveis de indentação usada pelo `LogosLexer`. Cada nível
+// guarda a contagem principal e a alternativa medidas por `TabPolicy::measure`
+#[derive(Debug, Clone)]
+pub(super) struct IndentStack {
+    levels: Vec<(usize, usize)>,
+}
+
+// Efeito da indentação de uma linha sobre a pilha: níveis fechados (DEDENTs), um novo
+// nível aberto depois deles (INDENT) e o erro de indentação, se houver
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct IndentChange {
+    pub(super) dedents: usize,
+    pub(super) indent: bool,
+    pub(super) error: Option<DiagnosticCode>,
+}
+
+impl IndentStack {
+    pub(super) fn new() -> Self {
+        IndentStack { levels: vec![(0, 0)] } // começamos no nível de indentação 0
+    }
+    
+    // Compara a indentação de uma linha com o topo da pilha, sem alterá-la
+    pub(super) fn compare(&self, indent: usize, alt_indent: usize) -> IndentChange {
+        let (last_indent, last_alt_indent) = self.levels[self.levels.len() - 1];
+        let tab_error = |inconsistent: bool| inconsistent.then_some(DiagnosticCode::TabError);
+        
+        if indent == last_indent {
+            return IndentChange { dedents: 0, indent: false, error: tab_error(alt_indent != last_alt_indent) };
+        }
+        if indent > last_indent {
+            return IndentChange { dedents: 0, indent: true, error: tab_error(alt_indent <= last_alt_indent) };
+        }
+        
+        // O novo nível precisa coincidir com algum nível externo já empilhado
+        let kept = self.levels.iter().rposition(|&(level, _)| level <= indent).unwrap_or(0);
+        let dedents = self.levels.len() - 1 - kept;
+        let (kept_indent, kept_alt_indent) = self.levels[kept];
+        
+        if kept_indent != indent {
+            // Para não repetir o erro nas linhas seguintes, o nível inválido passa a
+            // valer como um novo bloco
+            IndentChange { dedents, indent: true, error: Some(DiagnosticCode::InconsistentDedent) }
+        } else {
+            IndentChange { dedents, indent: false, error: tab_error(alt_indent != kept_alt_indent) }
+        }
+    }
+    
+    // Aplica à pilha uma mudança calculada por `compare` para a mesma indentação
+    pub(super) fn apply(&mut self, change: IndentChange, indent: usize, alt_indent: usize) {
+        self.levels.truncate(self.levels.len() - change.dedents);
+        if change.indent {
+            self.levels.push((indent, alt_indent));
+        }
+    }
+}
+
+// Posição no código-fonte onde começa o token sendo lido
+#[derive(Debug, Clone, Copy, Default)]
+struct Position {
+    offset: usize, // deslocamento em bytes
+    line: usize,
+    column: usize,
+}
+
+// Leitor caractere a caractere usado pelo `LogosLexer` para o que o logos não reconhece
+// sozinho: strings que precisam ser decodificadas, f-strings, literais numéricos
+// malformados e caracteres inesperados. Percorre o próprio código-fonte a partir de um
+// deslocamento em bytes, sem copiá-lo, e os literais dos tokens só são alocados quando
+// o valor difere do trecho de onde foi lido
+pub(super) struct Scanner<'src> {
+    source: &'src str,
+    offset: usize,        // posição em bytes do caractere atual no código-fonte
+    ch: char,             // caractere atual sendo examinado ('\0' no fim do código)
+    line: usize,          // linha atual
+    column: usize,        // coluna atual
+    file_id: usize,       // identificador do arquivo gravado nos spans
+    tab_policy: TabPolicy,
+    token_start: Position,      // início do token sendo lido
+    pub(super) tokens: Vec<BorrowedToken<'src>>, // tokens lidos até aqui
+    pub(super) diagnostics: Vec<Diagnostic>, // erros encontrados até aqui
+}
+
+impl<'src> Scanner<'src> {
+    // Cria um leitor posicionado no byte `offset` de `source`, que fica na linha e
+    // coluna informadas
+    pub(super) fn new(source: &'src str, tab_policy: TabPolicy, file_id: usize, offset: usize, line: usize, column: usize) -> Self {
+        Scanner {
+            source,
+            offset,
+            ch: source[offset..].chars().next().unwrap_or('\0'),
+            line,
+            column,
+            file_id,
+            tab_policy,
+            token_start: Position { offset, line, column },
+            tokens: Vec::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+    
+    // Deslocamento em bytes do caractere atual
+    pub(super) fn offset(&self) -> usize {
+        self.offset
+    }
+    
+    // Lê o token que começa na posição atual com `read`. Se a leitura falhar, o erro
+    // é registrado e o restante do lexema inválido vira um único token Illegal
+    pub(super) fn scan(&mut self, read: fn(&mut Self) -> Result<(), Diagnostic>) {
+        if let Err(diagnostic) = read(self) {
+            let token = self.recover(diagnostic);
+            self.tokens.push(token);
+        }
     }
     
     // Lê o próximo caractere e atualiza as posições
     fn read_char(&mut self) {
-        if self.read_position >= self.input.len() {
-            self.ch = '\0'; // EOF
-        } else {
-            self.ch = self.input[self.read_position];
+        if self.offset >= self.source.len() {
+            return;
         }
         
-        self.position = self.read_position;
-        self.read_position += 1;
-        
+        // A linha só avança quando deixamos o '\n' para trás, assim o próprio
+        // caractere de nova linha pertence à linha que ele termina
         if self.ch == '\n' {
             self.line += 1;
-            self.column = 0;
+            self.column = 1;
         } else {
             self.column += 1;
         }
+        
+        self.offset += self.ch.len_utf8();
+        self.ch = self.source[self.offset..].chars().next().unwrap_or('\0');
     }
     
     // Espia o próximo caractere sem avançar o ponteiro
     fn peek_char(&self) -> char {
-        if self.read_position >= self.input.len() {
-            '\0'
-        } else {
-            self.input[self.read_position]
+        self.peek_char_at(1)
+    }
+    
+    // Espia o caractere `offset` posições à frente do caractere atual
+    fn peek_char_at(&self, offset: usize) -> char {
+        self.source[self.offset..].chars().nth(offset).unwrap_or('\0')
+    }
+    
+    // Span que vai de `start` até o caractere atual, exclusivo
+    fn span_from(&self, start: usize) -> Span {
+        Span::new(self.file_id, start, self.offset)
+    }
+    
+    // Diagnóstico para o trecho que vai de `start` até o caractere atual. Erros sem
+    // extensão apontam ao menos para o próprio caractere atual
+    fn error(&self, code: DiagnosticCode, start: usize, message: String) -> Diagnostic {
+        let mut end = self.offset;
+        if end == start && self.offset < self.source.len() {
+            end += self.ch.len_utf8();
         }
+        Diagnostic::new(code, message, Span::new(self.file_id, start, end))
     }
     
-    // Avança até que encontre um caractere não-whitespace
-    fn skip_whitespace(&mut self) -> bool {
-        let mut found_newline = false;
+    // Registra um erro que impediu a leitura do token atual e descarta o restante do
+    // lexema inválido, que é devolvido como um único token Illegal
+    fn recover(&mut self, diagnostic: Diagnostic) -> BorrowedToken<'src> {
+        self.diagnostics.push(diagnostic);
+        
+        let start = self.token_start;
         
-        while self.ch.is_whitespace() && self.ch != '\n' {
+        // A análise precisa avançar ao menos um caractere para não repetir o erro
+        if self.offset == start.offset {
+            self.read_char();
+        }
+        while is_letter(self.ch) || is_digit(self.ch) || self.ch == '.' {
             self.read_char();
         }
         
-        // Se chegamos a uma nova linha, precisamos processar a indentação
-        if self.ch == '\n' {
-            found_newline = true;
+        let source = self.source;
+        BorrowedToken::new(TokenType::Illegal, &source[start.offset..self.offset], start.line, start.column, self.span_from(start.offset))
+    }
+    
+    // Token formado apenas pelo caractere atual, sem consumi-lo
+    fn single_char_token(&self, token_type: TokenType) -> BorrowedToken<'src> {
+        let source = self.source;
+        let end = self.offset + self.ch.len_utf8();
+        BorrowedToken::new(token_type, &source[self.offset..end], self.line, self.column, Span::new(self.file_id, self.offset, end))
+    }
+    
+    // Lê um caractere que não inicia nenhum token. A '\' só pode aparecer como último
+    // caractere da linha; os demais caracteres viram um token Illegal sem erro
+    pub(super) fn read_unexpected(&mut self) -> Result<(), Diagnostic> {
+        let start = self.offset;
+        
+        if self.ch == '\\' {
+            // Continuações válidas, seguidas de nova linha, já foram descartadas pelo logos
+            if self.peek_char() == '\r' {
+                self.read_char();
+            }
+            return Err(self.error(
+                DiagnosticCode::InvalidContinuation,
+                start,
+                format!(
+                    "Caractere inesperado após continuação de linha na linha {} coluna {}",
+                    self.line, self.column
+                ),
+            ));
+        }
+        
+        let token = self.single_char_token(TokenType::Illegal);
+        self.read_char();
+        self.tokens.push(token);
+        Ok(())
+    }
+    
+    // Lê um identificador (nome de variável, função, etc.) cujo fim o logos não
+    // reconheceu, como nomes com caracteres de versões mais novas do Unicode
+    pub(super) fn read_identifier(&mut self) -> Result<(), Diagnostic> {
+        let start = self.token_start;
+        
+        while is_identifier_continue(self.ch) {
+            self.read_char();
+        }
+        
+        // Nomes escritos de formas equivalentes, como "café" em NFC e em NFD, são o mesmo nome
+        let source = self.source;
+        let literal = normalize_identifier(&source[start.offset..self.offset]);
+        let token_type = lookup_identifier(&literal);
+        
+        self.tokens.push(BorrowedToken::new(token_type, literal, start.line, start.column, self.span_from(start.offset)));
+        Ok(())
+    }
+    
+    // Lê um número: inteiros decimais, hexadecimais (0x), octais (0o) e binários (0b),
+    // números de ponto flutuante com fração e/ou expoente e imaginários (sufixo j).
+    // Sublinhados são aceitos entre dígitos e o literal do token mantém o texto original
+    pub(super) fn read_number(&mut self) -> Result<(), Diagnostic> {
+        let start = self.token_start;
+        
+        // Inteiros com prefixo de base
+        if self.ch == '0' && matches!(self.peek_char(), 'x' | 'X' | 'o' | 'O' | 'b' | 'B') {
+            self.read_char();
+            let (radix, base_name) = match self.ch.to_ascii_lowercase() {
+                'x' => (16, "hexadecimal"),
+                'o' => (8, "octal"),
+                _ => (2, "binário"),
+            };
             self.read_char();
             
-            // Conta os espaços no início da linha
-            let mut space_count = 0;
-            while self.ch == ' ' || self.ch == '\t' {
-                if self.ch == '\t' {
-                    space_count += 4; // Assumindo que um tab equivale a 4 espaços
+            // Um sublinhado pode separar o prefixo do primeiro dígito (0x_FF)
+            if self.ch == '_' {
+                self.read_char();
+            }
+            if !self.ch.is_digit(radix) {
+                return Err(self.number_error(&format!("literal {} sem dígitos", base_name)));
+            }
+            
+            self.read_digits(radix)?;
+            
+            if self.ch.is_ascii_alphanumeric() {
+                let reason = if self.ch.is_ascii_digit() {
+                    format!("dígito '{}' inválido em literal {}", self.ch, base_name)
                 } else {
-                    space_count += 1;
-                }
+                    format!("sufixo '{}' inválido", self.ch)
+                };
                 self.read_char();
+                return Err(self.number_error(&reason));
             }
             
-            // Atualiza o nível de indentação atual
-            self.current_indent = space_count;
+            self.push_number(TokenType::Int);
+            return Ok(());
         }
         
-        found_newline
-    }
-    
-    // Gera tokens de indentação/desindentação quando necessário
-    fn process_indentation(&mut self) -> Option<Token> {
-        if self.pending_tokens.len() > 0 {
-            return Some(self.pending_tokens.remove(0));
-        }
+        let mut token_type = TokenType::Int;
         
-        // Compara o nível de indentação atual com o nível anterior
-        let last_indent = *self.indent_levels.last().unwrap_or(&0);
+        // Parte inteira (ausente em números como .5)
+        if self.ch != '.' {
+            self.read_digits(10)?;
+        }
         
-        if self.current_indent > last_indent {
-            // Indentação - adiciona um nível na pilha
-            self.indent_levels.push(self.current_indent);
-            return Some(Token::new(
-                TokenType::Indent,
-                String::from("INDENT"),
-                self.line,
-                self.column
-            ));
-        } else if self.current_indent < last_indent {
-            // Desindentação - remove um ou mais níveis da pilha
-            while self.current_indent < *self.indent_levels.last().unwrap_or(&0) {
-                self.indent_levels.pop();
-                self.pending_tokens.push(Token::new(
-                    TokenType::Dedent,
-                    String::from("DEDENT"),
-                    self.line,
-                    self.column
-                ));
+        // Fração (pode ser vazia, como em 3.)
+        if self.ch == '.' {
+            token_type = TokenType::Float;
+            self.read_char();
+            
+            if is_digit(self.ch) {
+                self.read_digits(10)?;
+            } else if self.ch == '_' {
+                self.read_char();
+                return Err(self.number_error("'_' deve estar entre dígitos"));
             }
+        }
+        
+        // Expoente
+        if self.ch == 'e' || self.ch == 'E' {
+            token_type = TokenType::Float;
+            self.read_char();
             
-            if self.pending_tokens.len() > 0 {
-                return Some(self.pending_tokens.remove(0));
+            if self.ch == '+' || self.ch == '-' {
+                self.read_char();
+            }
+            if !is_digit(self.ch) {
+                return Err(self.number_error("expoente sem dígitos"));
             }
+            self.read_digits(10)?;
         }
         
-        None
-    }
-    
-    // Retorna o próximo token
-    pub fn next_token(&mut self) -> Result<Token, String> {
-        // Verifica se há tokens pendentes (indentação/desindentação)
-        if let Some(token) = self.process_indentation() {
-            return Ok(token);
+        // Sufixo imaginário
+        if self.ch == 'j' || self.ch == 'J' {
+            token_type = TokenType::Imaginary;
+            self.read_char();
         }
         
-        // Pula espaços em branco e processa novas linhas
-        let found_newline = self.skip_whitespace();
+        if is_letter(self.ch) || is_digit(self.ch) {
+            let reason = format!("sufixo '{}' inválido", self.ch);
+            self.read_char();
+            return Err(self.number_error(&reason));
+        }
         
-        // Se encontramos uma nova linha, retornar NEWLINE
-        if found_newline {
-            // Depois de uma nova linha, verifique se há indentação/desindentação
-            if let Some(token) = self.process_indentation() {
-                return Ok(token);
+        if token_type == TokenType::Int {
+            // Como no Python, 0 à esquerda só é permitido no próprio zero (00, 0_0)
+            let digits = self.source[start.offset..self.offset].replace('_', "");
+            if digits.len() > 1 && digits.starts_with('0') && digits.chars().any(|ch| ch != '0') {
+                return Err(self.number_error(
+                    "zeros à esquerda não são permitidos em inteiros decimais; use o prefixo 0o para octais"
+                ));
             }
-            
-            // Se não houver indentação/desindentação, retorna NEWLINE
-            return Ok(Token::new(
-                TokenType::Newline,
-                String::from("\\n"),
-                self.line - 1, // A linha já foi incrementada quando processamos o \n
-                self.column
-            ));
         }
         
-        // Processa o token com base no caractere atual
-        let token = match self.ch {
-            '=' => {
-                if self.peek_char() == '=' {
-                    self.read_char();
-                    Token::new(TokenType::Eq, String::from("=="), self.line, self.column - 1)
-                } else {
-                    Token::new(TokenType::Assign, String::from("="), self.line, self.column)
-                }
-            },
-            '+' => {
-                if self.peek_char() == '=' {
-                    self.read_char();
-                    Token::new(TokenType::PlusAssign, String::from("+="), self.line, self.column - 1)
-                } else {
-                    Token::new(TokenType::Plus, String::from("+"), self.line, self.column)
-                }
-            },
-            '-' => {
-                if self.peek_char() == '=' {
-                    self.read_char();
-                    Token::new(TokenType::MinusAssign, String::from("-="), self.line, self.column - 1)
-                } else {
-                    Token::new(TokenType::Minus, String::from("-"), self.line, self.column)
-                }
-            },
-            '!' => {
-                if self.peek_char() == '=' {
+        self.push_number(token_type);
+        Ok(())
+    }
+    
+    // Guarda o literal numérico que vai do início do token até a posição atual
+    fn push_number(&mut self, token_type: TokenType) {
+        let start = self.token_start;
+        let source = self.source;
+        let literal = &source[start.offset..self.offset];
+        self.tokens.push(BorrowedToken::new(token_type, literal, start.line, start.column, self.span_from(start.offset)));
+    }
+    
+    // Lê uma sequência de dígitos na base informada, com sublinhados apenas entre dígitos
+    fn read_digits(&mut self, radix: u32) -> Result<(), Diagnostic> {
+        loop {
+            while self.ch.is_digit(radix) {
+                self.read_char();
+            }
+            
+            if self.ch != '_' {
+                return Ok(());
+            }
+            
+            self.read_char();
+            if !self.ch.is_digit(radix) {
+                let reason = if self.ch == '_' {
                     self.read_char();
-                    Token::new(TokenType::NotEq, String::from("!="), self.line, self.column - 1)
+                    "sublinhados consecutivos não são permitidos"
                 } else {
-                    Token::new(TokenType::Bang, String::from("!"), self.line, self.column)
-                }
-            },
-            '*' => {
-                if self.peek_char() == '*' {
+                    "'_' deve estar entre dígitos"
+                };
+                return Err(self.number_error(reason));
+            }
+        }
+    }
+    
+    // Erro de um literal numérico, que começa no início do token e foi lido até a posição atual
+    fn number_error(&self, reason: &str) -> Diagnostic {
+        let start = self.token_start;
+        let literal = &self.source[start.offset..self.offset];
+        self.error(
+            DiagnosticCode::InvalidNumber,
+            start.offset,
+            format!(
+                "Literal numérico inválido '{}': {} na linha {} coluna {}",
+                literal, reason, start.line, start.column
+            ),
+        )
+    }
+    
+    // Lê uma string (entre aspas simples ou duplas, ou entre aspas triplas), com o
+    // prefixo opcional r, b, f ou uma combinação deles. O literal do token contém o valor
+    // já decodificado; em strings de bytes cada caractere representa um byte (U+0000 a U+00FF)
+    pub(super) fn read_string(&mut self) -> Result<(), Diagnostic> {
+        let start = self.token_start;
+        
+        while self.ch.is_ascii_alphabetic() {
+            self.read_char();
+        }
+        
+        let prefix = self.source[start.offset..self.offset].to_ascii_lowercase();
+        if prefix.contains('f') {
+            return self.read_fstring(&prefix, start);
+        }
+        
+        let is_raw = prefix.contains('r');
+        let is_bytes = prefix.contains('b');
+        
+        let quote_type = self.ch; // ' ou "
+        let is_triple = self.peek_char() == quote_type && self.peek_char_at(2) == quote_type;
+        
+        // Consome as aspas de abertura
+        self.read_char();
+        if is_triple {
+            self.read_char();
+            self.read_char();
+        }
+        
+        let mut value = Text::new(self.source, self.offset);
+        
+        loop {
+            match self.ch {
+                '\0' => {
+                    return Err(self.unterminated_string(start));
+                },
+                '\n' if !is_triple => {
+                    return Err(self.unterminated_string(start));
+                },
+                '\r' if self.peek_char() == '\n' => {
+                    // Quebras de linha \r\n são normalizadas para \n
                     self.read_char();
-                    Token::new(TokenType::Power, String::from("**"), self.line, self.column - 1)
-                } else if self.peek_char() == '=' {
+                },
+                ch if ch == quote_type => {
+                    if !is_triple {
+                        self.read_char();
+                        break;
+                    }
+                    if self.peek_char() == quote_type && self.peek_char_at(2) == quote_type {
+                        self.read_char();
+                        self.read_char();
+                        self.read_char();
+                        break;
+                    }
+                    value.keep(ch, self.offset);
                     self.read_char();
-                    Token::new(TokenType::StarAssign, String::from("*="), self.line, self.column - 1)
-                } else {
-                    Token::new(TokenType::Asterisk, String::from("*"), self.line, self.column)
-                }
-            },
-            '/' => {
-                if self.peek_char() == '=' {
+                },
+                '\\' if is_raw => {
+                    // Em strings raw a barra é mantida, mas ainda impede que a aspa seguinte feche a string
+                    value.keep('\\', self.offset);
                     self.read_char();
-                    Token::new(TokenType::SlashAssign, String::from("/="), self.line, self.column - 1)
-                } else if self.peek_char() == '/' {
-                    // Comentário de linha única
-                    self.skip_comment();
-                    return self.next_token();
-                } else {
-                    Token::new(TokenType::Slash, String::from("/"), self.line, self.column)
-                }
-            },
-            '%' => Token::new(TokenType::Percent, String::from("%"), self.line, self.column),
-            '<' => {
-                if self.peek_char() == '=' {
+                    if self.ch != '\0' {
+                        value.keep(self.ch, self.offset);
+                        self.read_char();
+                    }
+                },
+                '\\' => {
+                    match self.read_escape_sequence(is_bytes) {
+                        Ok(Some(decoded)) => value.push(decoded),
+                        Ok(None) => {},
+                        Err(diagnostic) => self.diagnostics.push(diagnostic),
+                    }
+                },
+                ch if is_bytes && !ch.is_ascii() => {
+                    let diagnostic = self.error(
+                        DiagnosticCode::NonAsciiBytes,
+                        self.offset,
+                        format!(
+                            "Literais de bytes só podem conter caracteres ASCII na linha {} coluna {}",
+                            self.line, self.column
+                        ),
+                    );
+                    self.diagnostics.push(diagnostic);
                     self.read_char();
-                    Token::new(TokenType::LtEq, String::from("<="), self.line, self.column - 1)
-                } else {
-                    Token::new(TokenType::Lt, String::from("<"), self.line, self.column)
-                }
-            },
-            '>' => {
-                if self.peek_char() == '=' {
+                },
+                ch => {
+                    value.keep(ch, self.offset);
                     self.read_char();
-                    Token::new(TokenType::GtEq, String::from(">="), self.line, self.column - 1)
-                } else {
-                    Token::new(TokenType::Gt, String::from(">"), self.line, self.column)
                 }
+            }
+        }
+        
+        let token_type = if is_bytes { TokenType::Bytes } else { TokenType::String };
+        self.tokens.push(BorrowedToken::new(token_type, value.finish(), start.line, start.column, self.span_from(start.offset)));
+        Ok(())
+    }
+    
+    // Erro de uma string que chegou ao fim da linha ou do arquivo sem ser fechada
+    fn unterminated_string(&self, start: Position) -> Diagnostic {
+        self.error(
+            DiagnosticCode::UnterminatedString,
+            start.offset,
+            format!("String não fechada iniciada na linha {} coluna {}", start.line, start.column),
+        )
+    }
+    
+    // Decodifica uma sequência de escape iniciada pela barra atual. Retorna None
+    // para a barra seguida de nova linha, que apenas continua a string, e para a
+    // barra no fim do arquivo, que fica para a string reportar como não fechada
+    fn read_escape_sequence(&mut self, is_bytes: bool) -> Result<Option<char>, Diagnostic> {
+        let start = self.offset;
+        let line = self.line;
+        let column = self.column;
+        self.read_char(); // Consome a barra
+        
+        let escape = self.ch;
+        let decoded = match escape {
+            '\n' => {
+                self.read_char();
+                return Ok(None);
             },
-            ',' => Token::new(TokenType::Comma, String::from(","), self.line, self.column),
-            ';' => Token::new(TokenType::Semicolon, String::from(";"), self.line, self.column),
-            ':' => Token::new(TokenType::Colon, String::from(":"), self.line, self.column),
-            '.' => Token::new(TokenType::Dot, String::from("."), self.line, self.column),
-            '(' => Token::new(TokenType::LParen, String::from("("), self.line, self.column),
-            ')' => Token::new(TokenType::RParen, String::from(")"), self.line, self.column),
-            '{' => Token::new(TokenType::LBrace, String::from("{"), self.line, self.column),
-            '}' => Token::new(TokenType::RBrace, String::from("}"), self.line, self.column),
-            '[' => Token::new(TokenType::LBracket, String::from("["), self.line, self.column),
-            ']' => Token::new(TokenType::RBracket, String::from("]"), self.line, self.column),
-            '"' | '\'' => self.read_string()?,
-            '#' => {
-                // Comentário de linha única com #
-                self.skip_comment();
-                return self.next_token();
-            },
-            '\0' => {
-                // Antes de retornar EOF, precisamos gerar DEDENTs para todos os níveis de indentação pendentes
-                if self.indent_levels.len() > 1 {
-                    self.current_indent = 0;
-                    if let Some(token) = self.process_indentation() {
-                        return Ok(token);
+            '\0' => return Ok(None),
+            '\\' => '\\',
+            '\'' => '\'',
+            '"' => '"',
+            'a' => '\u{07}',
+            'b' => '\u{08}',
+            'f' => '\u{0C}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'v' => '\u{0B}',
+            '0'..='7' => {
+                // Até três dígitos octais
+                let mut code = 0;
+                for _ in 0..3 {
+                    match self.ch.to_digit(8) {
+                        Some(digit) => {
+                            code = code * 8 + digit;
+                            self.read_char();
+                        },
+                        None => break,
                     }
                 }
-                Token::new(TokenType::Eof, String::from(""), self.line, self.column)
-            },
-            _ => {
-                if is_letter(self.ch) {
-                    return Ok(self.read_identifier());
-                } else if is_digit(self.ch) {
-                    return Ok(self.read_number()?);
-                } else {
-                    Token::new(TokenType::Illegal, self.ch.to_string(), self.line, self.column)
+                
+                if is_bytes && code > 0xFF {
+                    return Err(self.error(
+                        DiagnosticCode::InvalidEscape,
+                        start,
+                        format!("Escape octal fora do intervalo de um byte na linha {} coluna {}", line, column),
+                    ));
                 }
+                // Valores octais de até três dígitos são sempre escalares válidos
+                return Ok(char::from_u32(code));
+            },
+            'x' => return self.read_hex_escape(2, start, line, column).map(Some),
+            'u' if !is_bytes => return self.read_hex_escape(4, start, line, column).map(Some),
+            'U' if !is_bytes => return self.read_hex_escape(8, start, line, column).map(Some),
+            other => {
+                self.read_char();
+                return Err(self.error(
+                    DiagnosticCode::InvalidEscape,
+                    start,
+                    format!("Sequência de escape inválida '\\{}' na linha {} coluna {}", other, line, column),
+                ));
             }
         };
         
         self.read_char();
-        Ok(token)
+        Ok(Some(decoded))
     }
     
-    // Lê um identificador (nome de variável, função, etc.)
-    fn read_identifier(&mut self) -> Token {
-        let position = self.position;
+    // Lê exatamente `digits` dígitos hexadecimais de um escape \x, \u ou \U
+    fn read_hex_escape(&mut self, digits: usize, start: usize, line: usize, column: usize) -> Result<char, Diagnostic> {
+        let escape = self.ch;
+        self.read_char(); // Consome o x, u ou U
         
-        while is_letter(self.ch) || is_digit(self.ch) {
-            self.read_char();
+        let mut code: u32 = 0;
+        for _ in 0..digits {
+            match self.ch.to_digit(16) {
+                Some(digit) => {
+                    code = code * 16 + digit;
+                    self.read_char();
+                },
+                None => {
+                    return Err(self.error(
+                        DiagnosticCode::InvalidEscape,
+                        start,
+                        format!(
+                            "Escape '\\{}' incompleto: esperados {} dígitos hexadecimais na linha {} coluna {}",
+                            escape, digits, line, column
+                        ),
+                    ));
+                }
+            }
         }
         
-        let literal: String = self.input[position..self.position].iter().collect();
-        let token_type = lookup_identifier(&literal);
-        
-        Token::new(token_type, literal, self.line, self.column - (self.position - position))
+        char::from_u32(code).ok_or_else(|| self.error(
+            DiagnosticCode::InvalidEscape,
+            start,
+            format!(
+                "Escape '\\{}' não representa um caractere Unicode válido na linha {} coluna {}",
+                escape, line, column
+            ),
+        ))
     }
     
-    // Lê um número (inteiro ou ponto flutuante)
-    fn read_number(&mut self) -> Result<Token, String> {
-        let position = self.position;
-        let mut is_float = false;
+    // Lê uma f-string. O resultado é a sequência FSTRING_START, partes literais
+    // (FSTRING_MIDDLE) e, para cada campo, '{' + tokens da expressão + texto de depuração
+    // (FSTRING_MIDDLE com "x=", em {x=}) + conversão ('!' + identificador) e especificação
+    // de formato (':' + FSTRING_MIDDLE e campos aninhados) + '}', terminando em
+    // FSTRING_END. Os tokens só são guardados se a f-string for lida até o fim
+    fn read_fstring(&mut self, prefix: &str, start: Position) -> Result<(), Diagnostic> {
+        let is_raw = prefix.contains('r');
+        let quote_type = self.ch;
+        let is_triple = self.peek_char() == quote_type && self.peek_char_at(2) == quote_type;
+        let quotes = if is_triple { 3 } else { 1 };
         
-        while is_digit(self.ch) {
+        // Consome as aspas de abertura
+        for _ in 0..quotes {
             self.read_char();
         }
         
-        // Verifica se é um número de ponto flutuante
-        if self.ch == '.' && is_digit(self.peek_char()) {
-            is_float = true;
-            self.read_char(); // Consome o ponto
-            
-            while is_digit(self.ch) {
-                self.read_char();
+        let source = self.source;
+        let mut tokens = vec![BorrowedToken::new(
+            TokenType::FStringStart,
+            format!("{}{}", prefix, &source[self.offset - quotes..self.offset]),
+            start.line,
+            start.column,
+            self.span_from(start.offset),
+        )];
+        
+        let mut text = Text::new(source, self.offset);
+        let mut text_line = self.line;
+        let mut text_column = self.column;
+        let mut text_start = self.offset;
+        
+        loop {
+            match self.ch {
+                '\0' => {
+                    return Err(self.unterminated_string(start));
+                },
+                '\n' if !is_triple => {
+                    return Err(self.unterminated_string(start));
+                },
+                '\r' if self.peek_char() == '\n' => {
+                    self.read_char();
+                },
+                ch if ch == quote_type
+                    && (!is_triple || (self.peek_char() == quote_type && self.peek_char_at(2) == quote_type)) =>
+                {
+                    if !text.is_empty() {
+                        tokens.push(BorrowedToken::new(
+                            TokenType::FStringMiddle,
+                            text.finish(),
+                            text_line,
+                            text_column,
+                            self.span_from(text_start),
+                        ));
+                    }
+                    
+                    let end_start = self.offset;
+                    let (end_line, end_column) = (self.line, self.column);
+                    for _ in 0..quotes {
+                        self.read_char();
+                    }
+                    tokens.push(BorrowedToken::new(
+                        TokenType::FStringEnd,
+                        &source[end_start..self.offset],
+                        end_line,
+                        end_column,
+                        self.span_from(end_start),
+                    ));
+                    break;
+                },
+                '{' if self.peek_char() == '{' => {
+                    text.push('{');
+                    self.read_char();
+                    self.read_char();
+                },
+                '}' if self.peek_char() == '}' => {
+                    text.push('}');
+                    self.read_char();
+                    self.read_char();
+                },
+                '{' => {
+                    if !text.is_empty() {
+                        tokens.push(BorrowedToken::new(
+                            TokenType::FStringMiddle,
+                            std::mem::replace(&mut text, Text::new(source, self.offset)).finish(),
+                            text_line,
+                            text_column,
+                            self.span_from(text_start),
+                        ));
+                    }
+                    self.read_fstring_field(&mut tokens)?;
+                    text = Text::new(source, self.offset);
+                    text_line = self.line;
+                    text_column = self.column;
+                    text_start = self.offset;
+                },
+                '}' => {
+                    let diagnostic = self.error(
+                        DiagnosticCode::InvalidFString,
+                        self.offset,
+                        format!("f-string: '}}' isolado não é permitido na linha {} coluna {}", self.line, self.column),
+                    );
+                    self.diagnostics.push(diagnostic);
+                    text.keep('}', self.offset);
+                    self.read_char();
+                },
+                '\\' if is_raw => {
+                    // Como em `read_string`, a barra é mantida e impede que a aspa seguinte
+                    // feche a f-string; '{' e '}' depois dela ainda delimitam campos
+                    text.keep('\\', self.offset);
+                    self.read_char();
+                    if !matches!(self.ch, '\0' | '{' | '}') {
+                        text.keep(self.ch, self.offset);
+                        self.read_char();
+                    }
+                },
+                '\\' => {
+                    match self.read_escape_sequence(false) {
+                        Ok(Some(decoded)) => text.push(decoded),
+                        Ok(None) => {},
+                        Err(diagnostic) => self.diagnostics.push(diagnostic),
+                    }
+                },
+                ch => {
+                    text.keep(ch, self.offset);
+                    self.read_char();
+                }
             }
         }
         
-        let literal: String = self.input[position..self.position].iter().collect();
-        let column = self.column - (self.position - position);
+        self.tokens.extend(tokens);
+        Ok(())
+    }
+    
+    // Lê um campo de substituição `{expressão!conversão:formato}` de uma f-string.
+    // Um campo não fechado interrompe a f-string; os demais erros são registrados e
+    // o campo é lido até o fim
+    fn read_fstring_field(&mut self, tokens: &mut Vec<BorrowedToken<'src>>) -> Result<(), Diagnostic> {
+        let source = self.source;
+        let field_start = self.offset;
+        let field_line = self.line;
+        let field_column = self.column;
+        tokens.push(self.single_char_token(TokenType::LBrace));
+        self.read_char(); // Consome o '{'
+        
+        let unclosed = |scanner: &Scanner| scanner.error(
+            DiagnosticCode::InvalidFString,
+            field_start,
+            format!("f-string: '{{' não fechado na linha {} coluna {}", field_line, field_column),
+        );
         
-        // Verifica se o literal pode ser convertido para um número válido
-        if is_float {
-            if literal.parse::<f64>().is_err() {
-                return Err(format!("Número de ponto flutuante inválido: {} na linha {} coluna {}", literal, self.line, column));
+        // Separa o código da expressão, que termina no primeiro '!', ':' ou '}' fora de
+        // parênteses, colchetes, chaves ou strings aninhadas
+        let expression_line = self.line;
+        let expression_column = self.column;
+        let expression_start = self.offset;
+        let mut is_valid = true;
+        let mut depth = 0;
+        let mut previous = '\0';
+        loop {
+            match self.ch {
+                '\0' => return Err(unclosed(self)),
+                '\n' if depth == 0 => return Err(unclosed(self)),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' if depth > 0 => depth -= 1,
+                '}' => break,
+                // ':' dentro de colchetes ou chaves faz parte da expressão: {xs[1:2]}, { {"a": 1} }
+                ':' if depth == 0 => break,
+                '!' if self.peek_char() != '=' && depth == 0 => break,
+                // '=' de depuração, que não faz parte de ==, !=, <= ou >=
+                '=' if depth == 0 && self.peek_char() != '=' && !matches!(previous, '=' | '!' | '<' | '>') => break,
+                '"' | '\'' => {
+                    // Strings aninhadas podem conter qualquer delimitador
+                    let quote = self.ch;
+                    self.read_char();
+                    while self.ch != quote {
+                        if self.ch == '\0' {
+                            return Err(unclosed(self));
+                        }
+                        if self.ch == '\\' {
+                            self.read_char();
+                        }
+                        self.read_char();
+                    }
+                },
+                '\\' | '#' => {
+                    let diagnostic = self.error(
+                        DiagnosticCode::InvalidFString,
+                        self.offset,
+                        format!(
+                            "f-string: a expressão não pode conter '{}' na linha {} coluna {}",
+                            self.ch, self.line, self.column
+                        ),
+                    );
+                    self.diagnostics.push(diagnostic);
+                    is_valid = false;
+                },
+                _ => {}
             }
-            Ok(Token::new(TokenType::Float, literal, self.line, column))
-        } else {
-            if literal.parse::<i64>().is_err() {
-                return Err(format!("Número inteiro inválido: {} na linha {} coluna {}", literal, self.line, column));
+            previous = self.ch;
+            self.read_char();
+        }
+        
+        if source[expression_start..self.offset].trim().is_empty() {
+            let diagnostic = self.error(
+                DiagnosticCode::InvalidFString,
+                field_start,
+                format!("f-string: expressão vazia não é permitida na linha {} coluna {}", field_line, field_column),
+            );
+            self.diagnostics.push(diagnostic);
+            is_valid = false;
+        }
+        
+        // A expressão é analisada por um lexer próprio, como se estivesse entre parênteses.
+        // Ele lê o mesmo código-fonte, só que limitado ao fim da expressão, para que as
+        // posições sejam as do arquivo
+        if is_valid {
+            let mut lexer = LogosLexer::for_expression(
+                &source[..self.offset],
+                self.tab_policy,
+                self.file_id,
+                expression_start,
+                expression_line,
+                expression_column,
+            );
+            loop {
+                let token = lexer.next_borrowed_recovering();
+                match token.token_type {
+                    TokenType::Eof => break,
+                    TokenType::Newline => {},
+                    _ => tokens.push(token),
+                }
             }
-            Ok(Token::new(TokenType::Int, literal, self.line, column))
+            self.diagnostics.append(&mut lexer.take_diagnostics());
         }
-    }
-    
-    // Lê uma string (entre aspas simples ou duplas)
-    fn read_string(&mut self) -> Result<Token, String> {
-        let quote_type = self.ch; // ' ou "
-        let start_line = self.line;
-        let start_column = self.column;
         
-        self.read_char(); // Consome a aspas de abertura
-        let position = self.position;
+        // Depuração opcional: {x = } escreve "x = " antes do valor. O texto da expressão,
+        // o '=' e os espaços seguintes formam um FSTRING_MIDDLE com o span do '=' e dos espaços
+        if self.ch == '=' {
+            let debug_line = self.line;
+            let debug_column = self.column;
+            let debug_start = self.offset;
+            self.read_char();
+            while self.ch == ' ' || self.ch == '\t' {
+                self.read_char();
+            }
+            
+            tokens.push(BorrowedToken::new(
+                TokenType::FStringMiddle,
+                &source[expression_start..self.offset],
+                debug_line,
+                debug_column,
+                self.span_from(debug_start),
+            ));
+            
+            if !matches!(self.ch, '!' | ':' | '}') {
+                let diagnostic = self.error(
+                    DiagnosticCode::InvalidFString,
+                    self.offset,
+                    format!(
+                        "f-string: esperado '!', ':' ou '}}' depois do '=' na linha {} coluna {}",
+                        self.line, self.column
+                    ),
+                );
+                self.diagnostics.push(diagnostic);
+                
+                while !matches!(self.ch, '!' | ':' | '}' | '\n' | '\0') {
+                    self.read_char();
+                }
+            }
+        }
         
-        // Lê até encontrar a aspas correspondente de fechamento
-        while self.ch != quote_type && self.ch != '\0' {
-            // Se encontrarmos um escape, avance um caractere adicional
-            if self.ch == '\\' && (self.peek_char() == quote_type || self.peek_char() == '\\') {
+        // Conversão opcional: !r, !s ou !a
+        if self.ch == '!' {
+            let bang = self.single_char_token(TokenType::Bang);
+            self.read_char();
+            
+            let conversion = self.ch;
+            if matches!(conversion, 'r' | 's' | 'a') && matches!(self.peek_char(), ':' | '}') {
+                tokens.push(bang);
+                tokens.push(self.single_char_token(TokenType::Identifier));
                 self.read_char();
+            } else {
+                let diagnostic = self.error(
+                    DiagnosticCode::InvalidFString,
+                    self.offset,
+                    format!(
+                        "f-string: conversão inválida na linha {} coluna {}, esperado 'r', 's' ou 'a'",
+                        self.line, self.column
+                    ),
+                );
+                self.diagnostics.push(diagnostic);
+                
+                while !matches!(self.ch, ':' | '}' | '\n' | '\0') {
+                    self.read_char();
+                }
             }
+        }
+        
+        // Especificação de formato opcional, lida literalmente até o '}'. Campos aninhados,
+        // como em {x:>{largura}}, são lidos como os demais campos
+        if self.ch == ':' {
+            tokens.push(self.single_char_token(TokenType::Colon));
             self.read_char();
+            
+            let mut spec_line = self.line;
+            let mut spec_column = self.column;
+            let mut spec_start = self.offset;
+            while self.ch != '}' {
+                match self.ch {
+                    '\0' | '\n' => return Err(unclosed(self)),
+                    '{' => {
+                        if self.offset > spec_start {
+                            tokens.push(BorrowedToken::new(
+                                TokenType::FStringMiddle,
+                                &source[spec_start..self.offset],
+                                spec_line,
+                                spec_column,
+                                self.span_from(spec_start),
+                            ));
+                        }
+                        self.read_fstring_field(tokens)?;
+                        spec_line = self.line;
+                        spec_column = self.column;
+                        spec_start = self.offset;
+                        continue;
+                    },
+                    _ => {},
+                }
+                self.read_char();
+            }
+            
+            if self.offset > spec_start {
+                tokens.push(BorrowedToken::new(
+                    TokenType::FStringMiddle,
+                    &source[spec_start..self.offset],
+                    spec_line,
+                    spec_column,
+                    self.span_from(spec_start),
+                ));
+            }
         }
         
-        // Verifica se a string foi fechada corretamente
-        if self.ch != quote_type {
-            return Err(format!("String não fechada iniciada na linha {} coluna {}", start_line, start_column));
+        if self.ch != '}' {
+            return Err(unclosed(self));
         }
         
-        let literal: String = self.input[position..self.position].iter().collect();
-        Ok(Token::new(TokenType::String, literal, start_line, start_column))
+        tokens.push(self.single_char_token(TokenType::RBrace));
+        self.read_char(); // Consome o '}'
+        Ok(())
+    }
+}
+
+// Valor decodificado de uma string ou de uma parte literal de f-string. Enquanto o valor
+// coincide com o código-fonte ele é apenas um trecho dele, e só passa a ser copiado quando
+// um escape, uma chave dupla ou uma quebra \r\n o torna diferente
+struct Text<'src> {
+    source: &'src str,
+    start: usize,
+    end: usize,
+    decoded: Option<String>, // cópia do valor, a partir do primeiro caractere alterado
+}
+
+impl<'src> Text<'src> {
+    fn new(source: &'src str, start: usize) -> Self {
+        Text { source, start, end: start, decoded: None }
     }
     
-    // Pula comentários (de # até o final da linha)
-    fn skip_comment(&mut self) {
-        while self.ch != '\n' && self.ch != '\0' {
-            self.read_char();
+    // Acrescenta o caractere `ch` do código-fonte, que começa no byte `offset`
+    fn keep(&mut self, ch: char, offset: usize) {
+        if self.decoded.is_none() && offset == self.end {
+            self.end += ch.len_utf8();
+        } else {
+            self.push(ch);
+        }
+    }
+    
+    // Acrescenta um caractere que não aparece assim no código-fonte
+    fn push(&mut self, ch: char) {
+        self.decoded.get_or_insert_with(|| self.source[self.start..self.end].to_string()).push(ch);
+    }
+    
+    fn is_empty(&self) -> bool {
+        self.decoded.as_ref().map_or(self.start == self.end, String::is_empty)
+    }
+    
+    fn finish(self) -> Cow<'src, str> {
+        match self.decoded {
+            Some(decoded) => Cow::Owned(decoded),
+            None => Cow::Borrowed(&self.source[self.start..self.end]),
         }
     }
 }
 
 // Funções auxiliares para verificar tipos de caracteres
 fn is_letter(ch: char) -> bool {
-    ch.is_alphabetic() || ch == '_'
+    is_identifier_start(ch)
 }
 
 fn is_digit(ch: char) -> bool {
     ch.is_ascii_digit()
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::DiagnosticCode;
+    use crate::lexer::tokenize;
+    use crate::lexer::token::int_literal_value;
+    use num_bigint::BigInt;
+
+    fn token_types(input: &str) -> Vec<TokenType> {
+        tokenize(input)
+            .expect("Lexer returned an error")
+            .into_iter()
+            .map(|token| token.token_type)
+            .collect()
+    }
+
+    #[test]
+    fn test_indent_stack() {
+        let mut stack = IndentStack::new();
+        
+        for (indent, alt_indent) in [(4, 4), (8, 8)] {
+            let change = stack.compare(indent, alt_indent);
+            assert_eq!(change, IndentChange { dedents: 0, indent: true, error: None });
+            stack.apply(change, indent, alt_indent);
+        }
+        
+        // Voltar ao nível 0 fecha os dois blocos; um nível intermediário é inconsistente
+        assert_eq!(stack.compare(0, 0), IndentChange { dedents: 2, indent: false, error: None });
+        assert_eq!(
+            stack.compare(2, 2),
+            IndentChange { dedents: 2, indent: true, error: Some(DiagnosticCode::InconsistentDedent) }
+        );
+        
+        // Mesma largura com outra combinação de tabs e espaços
+        assert_eq!(stack.compare(8, 1).error, Some(DiagnosticCode::TabError));
+    }
+
+    #[test]
+    fn test_indented_block() {
+        let input = "if x:\n    y\nz\n";
+
+        assert_eq!(
+            token_types(input),
+            vec![
+                TokenType::If,
+                TokenType::Identifier,
+                TokenType::Colon,
+                TokenType::Newline,
+                TokenType::Indent,
+                TokenType::Identifier,
+                TokenType::Newline,
+                TokenType::Dedent,
+                TokenType::Identifier,
+                TokenType::Newline,
+                TokenType::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blank_and_comment_lines_do_not_change_indentation() {
+        let input = "while x:\n    a\n\n# comentário\n        \n    b\n";
+
+        assert_eq!(
+            token_types(input),
+            vec![
+                TokenType::While,
+                TokenType::Identifier,
+                TokenType::Colon,
+                TokenType::Newline,
+                TokenType::Indent,
+                TokenType::Identifier,
+                TokenType::Newline,
+                TokenType::Identifier,
+                TokenType::Newline,
+                TokenType::Dedent,
+                TokenType::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_blocks_close_at_eof() {
+        let input = "func f():\n    if x:\n        pass";
+
+        let types = token_types(input);
+        let dedents = types.iter().filter(|t| **t == TokenType::Dedent).count();
+
+        assert_eq!(dedents, 2);
+        assert_eq!(&types[types.len() - 4..], &[
+            TokenType::Newline,
+            TokenType::Dedent,
+            TokenType::Dedent,
+            TokenType::Eof,
+        ]);
+    }
+
+    fn tokenize_with(mut lexer: LogosLexer) -> Result<Vec<TokenType>, String> {
+        let mut types = Vec::new();
+        loop {
+            let token = lexer.next_token()?;
+            let is_eof = token.token_type == TokenType::Eof;
+            types.push(token.token_type);
+            if is_eof {
+                return Ok(types);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inconsistent_dedent() {
+        let input = "if a:\n        b\n    c\n";
+
+        let err = tokenize(input).unwrap_err();
+        assert!(err.starts_with("Desindentação não corresponde"), "{}", err);
+        assert!(err.contains("linha 3"), "{}", err);
+    }
+
+    #[test]
+    fn test_mixed_tabs_and_spaces() {
+        let input = "if a:\n\tb\n        c\n";
+
+        let err = tokenize(input).unwrap_err();
+        assert!(err.starts_with("Uso inconsistente de tabs e espaços"), "{}", err);
+
+        // Apenas tabs é consistente na política padrão
+        assert!(tokenize("if a:\n\tb\n\tc\n").is_ok());
+    }
+
+    #[test]
+    fn test_configurable_tab_width() {
+        let input = "if a:\n\tb\n    c\n";
+
+        let types = tokenize_with(LogosLexer::with_tab_policy(input, TabPolicy::Width(4))).unwrap();
+        assert_eq!(types.iter().filter(|t| **t == TokenType::Indent).count(), 1);
+        assert_eq!(types.iter().filter(|t| **t == TokenType::Dedent).count(), 1);
+
+        let err = tokenize_with(LogosLexer::with_tab_policy(input, TabPolicy::Width(8))).unwrap_err();
+        assert!(err.starts_with("Desindentação não corresponde"), "{}", err);
+    }
+
+    #[test]
+    fn test_implicit_line_joining_inside_brackets() {
+        let input = "x = f(1,\n      2)\nitems = [\n    1,  # um\n\n  2,\n]\nd = {\n'a': 1}\n";
+
+        let types = token_types(input);
+
+        assert!(!types.contains(&TokenType::Indent));
+        assert!(!types.contains(&TokenType::Dedent));
+        assert_eq!(types.iter().filter(|t| **t == TokenType::Newline).count(), 3);
+    }
+
+    #[test]
+    fn test_backslash_continuation() {
+        let input = "if a and \\\n        b:\n    pass\n";
+
+        assert_eq!(
+            token_types(input),
+            vec![
+                TokenType::If,
+                TokenType::Identifier,
+                TokenType::And,
+                TokenType::Identifier,
+                TokenType::Colon,
+                TokenType::Newline,
+                TokenType::Indent,
+                TokenType::Pass,
+                TokenType::Newline,
+                TokenType::Dedent,
+                TokenType::Eof,
+            ]
+        );
+
+        let err = tokenize("x = 1 \\ 2\n").unwrap_err();
+        assert!(err.starts_with("Caractere inesperado após continuação de linha"), "{}", err);
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        let tokens = tokenize(r#""a\n\t\\\"b" 'é\x41\u00e9\101' "\
+c""#).unwrap();
+
+        assert_eq!(tokens[0].token_type, TokenType::String);
+        assert_eq!(tokens[0].literal, "a\n\t\\\"b");
+        assert_eq!(tokens[1].literal, "éAéA");
+        assert_eq!(tokens[2].literal, "c");
+
+        let err = tokenize(r#""\q""#).unwrap_err();
+        assert!(err.starts_with("Sequência de escape inválida '\\q'"), "{}", err);
+
+        let err = tokenize(r#""\x4""#).unwrap_err();
+        assert!(err.contains("esperados 2 dígitos hexadecimais"), "{}", err);
+    }
+
+    #[test]
+    fn test_triple_quoted_strings() {
+        let input = "func f():\n    \"\"\"Docstring\n    com \"aspas\" e ''.\"\"\"\n    pass\n";
+
+        let tokens = tokenize(input).unwrap();
+        let doc = tokens.iter().find(|t| t.token_type == TokenType::String).unwrap();
+
+        assert_eq!(doc.literal, "Docstring\n    com \"aspas\" e ''.");
+        assert_eq!((doc.line, doc.column), (2, 5));
+        assert_eq!(tokens.iter().filter(|t| t.token_type == TokenType::Indent).count(), 1);
+        assert_eq!(tokens[tokens.len() - 4].token_type, TokenType::Pass);
+
+        let err = tokenize("x = '''sem fim\n").unwrap_err();
+        assert!(err.starts_with("String não fechada iniciada na linha 1 coluna 5"), "{}", err);
+    }
+
+    #[test]
+    fn test_raw_and_byte_strings() {
+        let tokens = tokenize(r#"r"\d+\"" b'\x00\xffA' Rb"\n" bar"#).unwrap();
+
+        assert_eq!(tokens[0].token_type, TokenType::String);
+        assert_eq!(tokens[0].literal, r#"\d+\""#);
+        assert_eq!(tokens[1].token_type, TokenType::Bytes);
+        assert_eq!(tokens[1].literal, "\u{00}\u{ff}A");
+        assert_eq!(tokens[2].token_type, TokenType::Bytes);
+        assert_eq!(tokens[2].literal, r"\n");
+        assert_eq!(tokens[3].token_type, TokenType::Identifier);
+
+        let err = tokenize("b'é'").unwrap_err();
+        assert!(err.starts_with("Literais de bytes só podem conter caracteres ASCII"), "{}", err);
+    }
+
+    #[test]
+    fn test_fstring() {
+        let tokens = tokenize(r#"f"Hello {name}, total={a + b:.2f}!""#).unwrap();
+
+        let expected = vec![
+            (TokenType::FStringStart, "f\""),
+            (TokenType::FStringMiddle, "Hello "),
+            (TokenType::LBrace, "{"),
+            (TokenType::Identifier, "name"),
+            (TokenType::RBrace, "}"),
+            (TokenType::FStringMiddle, ", total="),
+            (TokenType::LBrace, "{"),
+            (TokenType::Identifier, "a"),
+            (TokenType::Plus, "+"),
+            (TokenType::Identifier, "b"),
+            (TokenType::Colon, ":"),
+            (TokenType::FStringMiddle, ".2f"),
+            (TokenType::RBrace, "}"),
+            (TokenType::FStringMiddle, "!"),
+            (TokenType::FStringEnd, "\""),
+            (TokenType::Newline, "\\n"),
+            (TokenType::Eof, ""),
+        ];
+
+        assert_eq!(tokens.len(), expected.len());
+        for (token, (token_type, literal)) in tokens.iter().zip(expected) {
+            assert_eq!(token.token_type, token_type);
+            assert_eq!(token.literal, literal);
+        }
+
+        // Posições dos tokens da expressão são relativas ao arquivo
+        assert_eq!((tokens[9].line, tokens[9].column), (1, 28));
+    }
+
+    #[test]
+    fn test_fstring_braces_conversion_and_nesting() {
+        let tokens = tokenize("f'{{x}} {d[\"k\"]!r} {f\"{y}\"}'").unwrap();
+        let types: Vec<TokenType> = tokens.iter().map(|t| t.token_type).collect();
+
+        assert_eq!(tokens[1].literal, "{x} ");
+        assert!(types.contains(&TokenType::Bang));
+        assert_eq!(types.iter().filter(|t| **t == TokenType::FStringStart).count(), 2);
+        assert_eq!(types.iter().filter(|t| **t == TokenType::FStringEnd).count(), 2);
+
+        let err = tokenize("f'{}'").unwrap_err();
+        assert!(err.starts_with("f-string: expressão vazia"), "{}", err);
+
+        let err = tokenize("f'a}'").unwrap_err();
+        assert!(err.starts_with("f-string: '}' isolado"), "{}", err);
+
+        let err = tokenize("f'{x'").unwrap_err();
+        assert!(err.starts_with("f-string: '{' não fechado"), "{}", err);
+
+        let err = tokenize("f'{x!z}'").unwrap_err();
+        assert!(err.starts_with("f-string: conversão inválida"), "{}", err);
+    }
+
+    #[test]
+    fn test_fstring_nested_format_spec_and_debug() {
+        let tokens = tokenize("f'{v!r:>{w}.2}'").unwrap();
+        let literals: Vec<&str> = tokens.iter().map(|t| t.literal.as_str()).collect();
+        assert_eq!(literals, vec!["f'", "{", "v", "!", "r", ":", ">", "{", "w", "}", ".2", "}", "'", "\\n", ""]);
+
+        // {x = } emite o texto "x = " depois da expressão, com o span do '=' e dos espaços
+        let input = "f'{x = }'";
+        let tokens = tokenize(input).unwrap();
+        assert_eq!(tokens[3].token_type, TokenType::FStringMiddle);
+        assert_eq!(tokens[3].literal, "x = ");
+        assert_eq!(&input[tokens[3].span.start..tokens[3].span.end], "= ");
+        assert_eq!(tokens[4].token_type, TokenType::RBrace);
+
+        // Operadores com '=' não são confundidos com a depuração
+        let tokens = tokenize("f'{a != b} {a <= b}'").unwrap();
+        assert!(tokens.iter().all(|t| t.token_type != TokenType::FStringMiddle || t.literal == " "));
+
+        let err = tokenize("f'{x=1}'").unwrap_err();
+        assert!(err.starts_with("f-string: esperado '!', ':' ou '}' depois do '='"), "{}", err);
+    }
+
+    #[test]
+    fn test_fstring_colon_inside_brackets_and_raw_quote() {
+        // ':' de fatias e dicionários não inicia a especificação de formato
+        let tokens = tokenize("f'{xs[1:2]}'").unwrap();
+        let literals: Vec<&str> = tokens.iter().map(|t| t.literal.as_str()).collect();
+        assert_eq!(literals, vec!["f'", "{", "xs", "[", "1", ":", "2", "]", "}", "'", "\\n", ""]);
+
+        let (tokens, diagnostics) = crate::lexer::tokenize_with_diagnostics("f'{ {\"a\": 1}[\"a\"] }'");
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+        let types: Vec<TokenType> = tokens.iter().map(|t| t.token_type).collect();
+        assert_eq!(&types[..4], &[TokenType::FStringStart, TokenType::LBrace, TokenType::LBrace, TokenType::String]);
+        assert_eq!(tokens[4].token_type, TokenType::Colon);
+        assert_eq!(tokens[tokens.len() - 4].token_type, TokenType::RBrace);
+
+        // Em f-strings raw a barra impede que a aspa seguinte feche a f-string
+        let tokens = tokenize("rf\"\\\"\" x").unwrap();
+        assert_eq!(tokens[1].token_type, TokenType::FStringMiddle);
+        assert_eq!(tokens[1].literal, "\\\"");
+        assert_eq!(tokens[2].token_type, TokenType::FStringEnd);
+        assert_eq!(tokens[3].literal, "x");
+
+        // '{' depois da barra ainda abre um campo
+        let tokens = tokenize("rf'\\{x}'").unwrap();
+        let literals: Vec<&str> = tokens.iter().map(|t| t.literal.as_str()).collect();
+        assert_eq!(literals, vec!["rf'", "\\", "{", "x", "}", "'", "\\n", ""]);
+    }
+
+    #[test]
+    fn test_numeric_literals() {
+        let input = "0xFF 0o755 0b1010 0X_ff 1_000_000 0 00 1e-9 2.5E+3 .5 3. 1_0.0_1 10j 1.5e3J x[1:3]";
+
+        let expected = vec![
+            (TokenType::Int, "0xFF"),
+            (TokenType::Int, "0o755"),
+            (TokenType::Int, "0b1010"),
+            (TokenType::Int, "0X_ff"),
+            (TokenType::Int, "1_000_000"),
+            (TokenType::Int, "0"),
+            (TokenType::Int, "00"),
+            (TokenType::Float, "1e-9"),
+            (TokenType::Float, "2.5E+3"),
+            (TokenType::Float, ".5"),
+            (TokenType::Float, "3."),
+            (TokenType::Float, "1_0.0_1"),
+            (TokenType::Imaginary, "10j"),
+            (TokenType::Imaginary, "1.5e3J"),
+            (TokenType::Identifier, "x"),
+            (TokenType::LBracket, "["),
+            (TokenType::Int, "1"),
+            (TokenType::Colon, ":"),
+            (TokenType::Int, "3"),
+        ];
+
+        let tokens = tokenize(input).unwrap();
+        for (token, (token_type, literal)) in tokens.iter().zip(expected) {
+            assert_eq!(token.token_type, token_type);
+            assert_eq!(token.literal, literal);
+        }
+
+        assert_eq!(int_literal_value("0xFF"), Some(BigInt::from(255)));
+        assert_eq!(int_literal_value("0o755"), Some(BigInt::from(493)));
+        assert_eq!(int_literal_value("1_000_000"), Some(BigInt::from(1_000_000)));
+    }
+
+    #[test]
+    fn test_integer_literals_have_no_size_limit() {
+        let tokens = tokenize("123456789012345678901234567890 0x1_0000_0000_0000_0000_0000").unwrap();
+
+        assert_eq!(tokens[0].token_type, TokenType::Int);
+        assert_eq!(
+            int_literal_value(&tokens[0].literal).unwrap().to_string(),
+            "123456789012345678901234567890"
+        );
+        assert_eq!(
+            int_literal_value(&tokens[1].literal),
+            Some(BigInt::from(1u8) << 80)
+        );
+    }
+
+    #[test]
+    fn test_malformed_numeric_literals() {
+        let cases = vec![
+            ("0x", "Literal numérico inválido '0x': literal hexadecimal sem dígitos"),
+            ("0b102", "Literal numérico inválido '0b102': dígito '2' inválido em literal binário"),
+            ("1_", "Literal numérico inválido '1_': '_' deve estar entre dígitos"),
+            ("1__0", "Literal numérico inválido '1__': sublinhados consecutivos não são permitidos"),
+            ("1e", "Literal numérico inválido '1e': expoente sem dígitos"),
+            ("1e+", "Literal numérico inválido '1e+': expoente sem dígitos"),
+            ("012", "Literal numérico inválido '012': zeros à esquerda não são permitidos"),
+            ("12abc", "Literal numérico inválido '12a': sufixo 'a' inválido"),
+        ];
+
+        for (input, expected) in cases {
+            let err = tokenize(input).unwrap_err();
+            assert!(err.starts_with(expected), "{}: {}", input, err);
+        }
+    }
+
+    #[test]
+    fn test_operators() {
+        let input = "// //= % %= ** **= & &= | |= ^ ^= ~ << <<= >> >>= -> @ @= := < <= > >= : -";
+
+        let expected = vec![
+            (TokenType::FloorDiv, "//"),
+            (TokenType::FloorDivAssign, "//="),
+            (TokenType::Percent, "%"),
+            (TokenType::PercentAssign, "%="),
+            (TokenType::Power, "**"),
+            (TokenType::PowerAssign, "**="),
+            (TokenType::Ampersand, "&"),
+            (TokenType::AmpersandAssign, "&="),
+            (TokenType::Pipe, "|"),
+            (TokenType::PipeAssign, "|="),
+            (TokenType::Caret, "^"),
+            (TokenType::CaretAssign, "^="),
+            (TokenType::Tilde, "~"),
+            (TokenType::ShiftLeft, "<<"),
+            (TokenType::ShiftLeftAssign, "<<="),
+            (TokenType::ShiftRight, ">>"),
+            (TokenType::ShiftRightAssign, ">>="),
+            (TokenType::Arrow, "->"),
+            (TokenType::At, "@"),
+            (TokenType::AtAssign, "@="),
+            (TokenType::Walrus, ":="),
+            (TokenType::Lt, "<"),
+            (TokenType::LtEq, "<="),
+            (TokenType::Gt, ">"),
+            (TokenType::GtEq, ">="),
+            (TokenType::Colon, ":"),
+            (TokenType::Minus, "-"),
+        ];
+
+        let tokens = tokenize(input).unwrap();
+        assert_eq!(tokens.len(), expected.len() + 2);
+        for (token, (token_type, literal)) in tokens.iter().zip(expected) {
+            assert_eq!(token.token_type, token_type);
+            assert_eq!(token.literal, literal);
+        }
+
+        // Colunas de operadores com três caracteres apontam para o início
+        let tokens = tokenize("a <<= 1").unwrap();
+        assert_eq!(tokens[1].column, 3);
+    }
+
+    #[test]
+    fn test_token_positions() {
+        let mut lexer = LogosLexer::new("a\n  bc");
+
+        let a = lexer.next_token().unwrap();
+        let newline = lexer.next_token().unwrap();
+        let indent = lexer.next_token().unwrap();
+        let bc = lexer.next_token().unwrap();
+
+        assert_eq!((a.line, a.column), (1, 1));
+        assert_eq!((newline.line, newline.column), (1, 2));
+        assert_eq!(indent.token_type, TokenType::Indent);
+        assert_eq!((bc.line, bc.column), (2, 3));
+    }
+
+    #[test]
+    fn test_error_recovery_collects_diagnostics() {
+        let input = "a = 0x\nb = \"\\q\" + 12abc\nc = 'sem fim\nif d:\n        e\n    f\n";
+        let (tokens, diagnostics) = crate::lexer::tokenize_with_diagnostics(input);
+
+        let codes: Vec<DiagnosticCode> = diagnostics.iter().map(|d| d.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                DiagnosticCode::InvalidNumber,
+                DiagnosticCode::InvalidEscape,
+                DiagnosticCode::InvalidNumber,
+                DiagnosticCode::UnterminatedString,
+                DiagnosticCode::InconsistentDedent,
+            ]
+        );
+        assert_eq!(&input[diagnostics[0].span.start..diagnostics[0].span.end], "0x");
+        assert_eq!(&input[diagnostics[1].span.start..diagnostics[1].span.end], "\\q");
+
+        let illegal: Vec<&str> = tokens
+            .iter()
+            .filter(|t| t.token_type == TokenType::Illegal)
+            .map(|t| t.literal.as_str())
+            .collect();
+        assert_eq!(illegal, vec!["0x", "12abc", "'sem fim"]);
+
+        // A análise continua depois dos erros, inclusive da string com escape inválido
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::String));
+        assert!(tokens.iter().any(|t| t.token_type == TokenType::Identifier && t.literal == "f"));
+        assert_eq!(tokens.last().unwrap().token_type, TokenType::Eof);
+
+        // Os INDENTs e DEDENTs continuam balanceados
+        let indents = tokens.iter().filter(|t| t.token_type == TokenType::Indent).count();
+        let dedents = tokens.iter().filter(|t| t.token_type == TokenType::Dedent).count();
+        assert_eq!(indents, dedents);
+
+        // Sem erros, a lista de diagnósticos fica vazia
+        let (_, diagnostics) = crate::lexer::tokenize_with_diagnostics("x = f'{y!r:>4}'\n");
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_token_spans_are_byte_offsets() {
+        let input = "café <<= 0x1F\nf\"é{ação!r}\"";
+        let tokens = tokenize(input).unwrap();
+
+        let spans: Vec<(TokenType, &str)> = tokens
+            .iter()
+            .map(|token| (token.token_type, &input[token.span.start..token.span.end]))
+            .collect();
+
+        assert_eq!(
+            spans,
+            vec![
+                (TokenType::Identifier, "café"),
+                (TokenType::ShiftLeftAssign, "<<="),
+                (TokenType::Int, "0x1F"),
+                (TokenType::Newline, "\n"),
+                (TokenType::FStringStart, "f\""),
+                (TokenType::FStringMiddle, "é"),
+                (TokenType::LBrace, "{"),
+                (TokenType::Identifier, "ação"),
+                (TokenType::Bang, "!"),
+                (TokenType::Identifier, "r"),
+                (TokenType::RBrace, "}"),
+                (TokenType::FStringEnd, "\""),
+                (TokenType::Newline, ""),
+                (TokenType::Eof, ""),
+            ]
+        );
+
+        let mut lexer = LogosLexer::new("x");
+        lexer.set_file_id(7);
+        assert_eq!(lexer.next_token().unwrap().span, Span::new(7, 0, 1));
+    }
+}
@@ -0,0 +1,706 @@
+use std::collections::VecDeque;
+use logos::Logos;
+use super::diagnostic::{Diagnostic, DiagnosticCode};
+use super::lexer::{IndentStack, Scanner, TabPolicy};
+use super::token::{BorrowedToken, Span, Token, TokenType, lookup_identifier};
+use super::unicode::{is_identifier_continue, is_identifier_start, normalize_identifier};
+
+
+
+// Tokens reconhecidos diretamente pelo logos. Espaços, comentários e continuações
+// com '\' são descartados aqui; indentação, linha/coluna e strings com escapes ou
+// f-strings ficam a cargo do `LogosLexer`
+#[derive(Logos, Debug, PartialEq, Clone, Copy)]
+#[logos(skip r"[^\S\n]+")]
+#[logos(skip r"#[^\n]*")]
+#[logos(skip r"\\\r?\n")]
+enum RawToken {
+    #[token("\n")]
+    Newline,
+
+    #[regex(r"[\p{XID_Start}_]\p{XID_Continue}*")]
+    Name,
+
+    #[regex(r"0[xX](_?[0-9a-fA-F])+")]
+    #[regex(r"0[oO](_?[0-7])+")]
+    #[regex(r"0[bB](_?[01])+")]
+    #[regex(r"[0-9](_?[0-9])*")]
+    Int,
+
+    #[regex(r"[0-9](_?[0-9])*\.([0-9](_?[0-9])*)?([eE][+-]?[0-9](_?[0-9])*)?")]
+    #[regex(r"\.[0-9](_?[0-9])*([eE][+-]?[0-9](_?[0-9])*)?")]
+    #[regex(r"[0-9](_?[0-9])*[eE][+-]?[0-9](_?[0-9])*")]
+    Float,
+
+    #[regex(r"[0-9](_?[0-9])*(\.([0-9](_?[0-9])*)?)?([eE][+-]?[0-9](_?[0-9])*)?[jJ]")]
+    #[regex(r"\.[0-9](_?[0-9])*([eE][+-]?[0-9](_?[0-9])*)?[jJ]")]
+    Imaginary,
+
+    // Strings completas (exceto f-strings), com prefixos r e b
+    #[regex(r#"([rRbB]|[rR][bB]|[bB][rR])?"([^"\\\n]|\\(.|\n))*""#)]
+    #[regex(r#"([rRbB]|[rR][bB]|[bB][rR])?'([^'\\\n]|\\(.|\n))*'"#)]
+    #[regex(r#"([rRbB]|[rR][bB]|[bB][rR])?"""([^"\\]|\\(.|\n)|"[^"\\]|"\\(.|\n)|""[^"\\]|""\\(.|\n))*""""#)]
+    #[regex(r#"([rRbB]|[rR][bB]|[bB][rR])?'''([^'\\]|\\(.|\n)|'[^'\\]|'\\(.|\n)|''[^'\\]|''\\(.|\n))*'''"#)]
+    String,
+
+    // Início de uma f-string ou de uma string que não pôde ser reconhecida inteira
+    #[regex(r#"([rRbBfF]|[rR][bBfF]|[bB][rR]|[fF][rR])?["']"#)]
+    StringStart,
+
+    #[token("=")] Assign,
+    #[token("==")] Eq,
+    #[token("+")] Plus,
+    #[token("+=")] PlusAssign,
+    #[token("-")] Minus,
+    #[token("-=")] MinusAssign,
+    #[token("->")] Arrow,
+    #[token("!")] Bang,
+    #[token("!=")] NotEq,
+    #[token("*")] Asterisk,
+    #[token("*=")] StarAssign,
+    #[token("**")] Power,
+    #[token("**=")] PowerAssign,
+    #[token("/")] Slash,
+    #[token("/=")] SlashAssign,
+    #[token("//")] FloorDiv,
+    #[token("//=")] FloorDivAssign,
+    #[token("%")] Percent,
+    #[token("%=")] PercentAssign,
+    #[token("<")] Lt,
+    #[token("<=")] LtEq,
+    #[token("<<")] ShiftLeft,
+    #[token("<<=")] ShiftLeftAssign,
+    #[token(">")] Gt,
+    #[token(">=")] GtEq,
+    #[token(">>")] ShiftRight,
+    #[token(">>=")] ShiftRightAssign,
+    #[token("&")] Ampersand,
+    #[token("&=")] AmpersandAssign,
+    #[token("|")] Pipe,
+    #[token("|=")] PipeAssign,
+    #[token("^")] Caret,
+    #[token("^=")] CaretAssign,
+    #[token("@")] At,
+    #[token("@=")] AtAssign,
+    #[token("~")] Tilde,
+    #[token(",")] Comma,
+    #[token(";")] Semicolon,
+    #[token(":")] Colon,
+    #[token(":=")] Walrus,
+    #[token(".")] Dot,
+    #[token("(")] LParen,
+    #[token(")")] RParen,
+    #[token("[")] LBracket,
+    #[token("]")] RBracket,
+    #[token("{")] LBrace,
+    #[token("}")] RBrace,
+}
+
+impl RawToken {
+    // Tipo do token para os tokens brutos que não precisam de tratamento especial
+    fn token_type(self) -> Option<TokenType> {
+        let token_type = match self {
+            RawToken::Int => TokenType::Int,
+            RawToken::Float => TokenType::Float,
+            RawToken::Imaginary => TokenType::Imaginary,
+            RawToken::Assign => TokenType::Assign,
+            RawToken::Eq => TokenType::Eq,
+            RawToken::Plus => TokenType::Plus,
+            RawToken::PlusAssign => TokenType::PlusAssign,
+            RawToken::Minus => TokenType::Minus,
+            RawToken::MinusAssign => TokenType::MinusAssign,
+            RawToken::Arrow => TokenType::Arrow,
+            RawToken::Bang => TokenType::Bang,
+            RawToken::NotEq => TokenType::NotEq,
+            RawToken::Asterisk => TokenType::Asterisk,
+            RawToken::StarAssign => TokenType::StarAssign,
+            RawToken::Power => TokenType::Power,
+            RawToken::PowerAssign => TokenType::PowerAssign,
+            RawToken::Slash => TokenType::Slash,
+            RawToken::SlashAssign => TokenType::SlashAssign,
+            RawToken::FloorDiv => TokenType::FloorDiv,
+            RawToken::FloorDivAssign => TokenType::FloorDivAssign,
+            RawToken::Percent => TokenType::Percent,
+            RawToken::PercentAssign => TokenType::PercentAssign,
+            RawToken::Lt => TokenType::Lt,
+            RawToken::LtEq => TokenType::LtEq,
+            RawToken::ShiftLeft => TokenType::ShiftLeft,
+            RawToken::ShiftLeftAssign => TokenType::ShiftLeftAssign,
+            RawToken::Gt => TokenType::Gt,
+            RawToken::GtEq => TokenType::GtEq,
+            RawToken::ShiftRight => TokenType::ShiftRight,
+            RawToken::ShiftRightAssign => TokenType::ShiftRightAssign,
+            RawToken::Ampersand => TokenType::Ampersand,
+            RawToken::AmpersandAssign => TokenType::AmpersandAssign,
+            RawToken::Pipe => TokenType::Pipe,
+            RawToken::PipeAssign => TokenType::PipeAssign,
+            RawToken::Caret => TokenType::Caret,
+            RawToken::CaretAssign => TokenType::CaretAssign,
+            RawToken::At => TokenType::At,
+            RawToken::AtAssign => TokenType::AtAssign,
+            RawToken::Tilde => TokenType::Tilde,
+            RawToken::Comma => TokenType::Comma,
+            RawToken::Semicolon => TokenType::Semicolon,
+            RawToken::Colon => TokenType::Colon,
+            RawToken::Walrus => TokenType::Walrus,
+            RawToken::Dot => TokenType::Dot,
+            RawToken::LParen => TokenType::LParen,
+            RawToken::RParen => TokenType::RParen,
+            RawToken::LBracket => TokenType::LBracket,
+            RawToken::RBracket => TokenType::RBracket,
+            RawToken::LBrace => TokenType::LBrace,
+            RawToken::RBrace => TokenType::RBrace,
+            RawToken::Newline | RawToken::Name | RawToken::String | RawToken::StringStart => return None,
+        };
+        Some(token_type)
+    }
+}
+
+/// Lexer do Odin, usado por `tokenize`, `tokenize_borrowed` e `tokenize_with_diagnostics`.
+///
+/// O logos reconhece os tokens e uma segunda etapa gera NEWLINE, INDENT e DEDENT,
+/// calcula linhas e colunas e decodifica strings. Strings com escapes, f-strings,
+/// literais numéricos malformados e caracteres inesperados são lidos por um `Scanner`
+/// sobre o mesmo código-fonte; erros são registrados como diagnósticos e o trecho
+/// inválido vira um token `Illegal`, sem interromper a análise.
+///
+/// `next_borrowed` retorna tokens que referenciam o código-fonte, sem copiar os
+/// literais; `next_token` retorna tokens com literais próprios
+pub struct LogosLexer<'src> {
+    source: &'src str,
+    raw: logos::Lexer<'src, RawToken>,
+    tab_policy: TabPolicy,
+    file_id: usize,
+    position: usize, // deslocamento em bytes até onde linha e coluna foram calculadas
+    line: usize,
+    column: usize,
+    line_start: usize, // deslocamento em bytes do início da linha física atual
+    indent_stack: IndentStack,
+    pending_tokens: VecDeque<BorrowedToken<'src>>,
+    at_line_start: bool,
+    line_has_content: bool,
+    bracket_depth: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'src> LogosLexer<'src> {
+    pub fn new(input: &'src str) -> Self {
+        Self::with_tab_policy(input, TabPolicy::default())
+    }
+
+    pub fn with_tab_policy(input: &'src str, tab_policy: TabPolicy) -> Self {
+        // Um '\0' marca o fim do código; o que vem depois dele é ignorado
+        let input = &input[..input.find('\0').unwrap_or(input.len())];
+        Self::starting_at(input, tab_policy, 0, 1, 1)
+    }
+
+    // Lexer para a expressão de um campo de f-string, que vai do byte `offset` até o fim
+    // de `source` e começa na linha e coluna informadas. A expressão é analisada como se
+    // estivesse entre parênteses
+    pub(super) fn for_expression(
+        source: &'src str,
+        tab_policy: TabPolicy,
+        file_id: usize,
+        offset: usize,
+        line: usize,
+        column: usize,
+    ) -> Self {
+        let mut lexer = Self::starting_at(source, tab_policy, offset, line, column);
+        lexer.file_id = file_id;
+        lexer.at_line_start = false;
+        lexer.bracket_depth = 1;
+        lexer
+    }
+
+    // Lexer que começa no byte `offset` de `source`, na linha e coluna informadas
+    fn starting_at(source: &'src str, tab_policy: TabPolicy, offset: usize, line: usize, column: usize) -> Self {
+        let mut raw = RawToken::lexer(source);
+        raw.bump(offset);
+
+        LogosLexer {
+            source,
+            raw,
+            tab_policy,
+            file_id: 0,
+            position: offset,
+            line,
+            column,
+            line_start: offset,
+            indent_stack: IndentStack::new(),
+            pending_tokens: VecDeque::new(),
+            at_line_start: true,
+            line_has_content: false,
+            bracket_depth: 0,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Define o identificador de arquivo usado nos spans dos tokens
+    pub fn set_file_id(&mut self, file_id: usize) {
+        self.file_id = file_id;
+    }
+
+    /// Erros encontrados até o momento
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Remove e retorna os erros encontrados até o momento
+    pub fn take_diagnostics(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.diagnostics)
+    }
+
+    /// Retorna o próximo token, parando no primeiro erro léxico
+    pub fn next_token(&mut self) -> Result<Token, String> {
+        self.next_borrowed().map(BorrowedToken::into_owned)
+    }
+
+    /// Retorna o próximo token sem interromper a análise em erros: trechos inválidos
+    /// viram tokens `Illegal` e os erros ficam disponíveis em `diagnostics`
+    pub fn next_token_recovering(&mut self) -> Token {
+        self.next_borrowed_recovering().into_owned()
+    }
+
+    /// Como `next_token`, mas o literal do token referencia o código-fonte
+    pub fn next_borrowed(&mut self) -> Result<BorrowedToken<'src>, String> {
+        let token = self.next_borrowed_recovering();
+
+        match self.diagnostics.first() {
+            Some(diagnostic) => Err(diagnostic.message.clone()),
+            None => Ok(token),
+        }
+    }
+
+    /// Como `next_token_recovering`, mas o literal do token referencia o código-fonte
+    pub fn next_borrowed_recovering(&mut self) -> BorrowedToken<'src> {
+        loop {
+            if let Some(token) = self.pending_tokens.pop_front() {
+                return token;
+            }
+            self.scan_tokens();
+        }
+    }
+
+    // Lê o próximo token bruto e enfileira os tokens correspondentes
+    fn scan_tokens(&mut self) {
+        let raw = match self.raw.next() {
+            Some(raw) => raw,
+            None => return self.end_of_input(),
+        };
+        let start = self.raw.span().start;
+
+        // Dentro de parênteses a linha continua, e linhas em branco ou só com
+        // comentários não geram NEWLINE nem alteram a indentação
+        if raw == Ok(RawToken::Newline) && (self.bracket_depth > 0 || (self.at_line_start && self.is_blank_line(start))) {
+            self.line_start = start + 1;
+            return;
+        }
+
+        if self.at_line_start {
+            self.at_line_start = false;
+            self.process_indentation();
+        }
+
+        if raw == Ok(RawToken::Newline) {
+            let (line, column) = self.position_of(start);
+            self.line_start = start + 1;
+            self.line_has_content = false;
+            self.at_line_start = true;
+            let span = Span::new(self.file_id, start, start + 1);
+            self.pending_tokens.push_back(BorrowedToken::new(TokenType::Newline, "\\n", line, column, span));
+            return;
+        }
+        self.line_has_content = true;
+
+        let (line, column) = self.position_of(start);
+        let slice = self.raw.slice();
+        let span = Span::new(self.file_id, start, start + slice.len());
+
+        // Quando uma string não fechada deixa de casar com o padrão completo, o logos não
+        // volta atrás para o início da string e devolve um erro ou o nome do prefixo
+        if matches!(raw, Err(()) | Ok(RawToken::Name)) && starts_string(&self.source[start..]) {
+            return self.scan_with(start, line, column, Scanner::read_string);
+        }
+
+        let raw = match raw {
+            Ok(raw) => raw,
+            // Caracteres que o logos não reconhece: nomes com caracteres que ele não conhece,
+            // '\' fora do fim da linha ou caracteres que não iniciam nenhum token
+            Err(()) if slice.starts_with(is_identifier_start) => return self.scan_with(start, line, column, Scanner::read_identifier),
+            Err(()) => return self.scan_with(start, line, column, Scanner::read_unexpected),
+        };
+
+        let token = match raw {
+            RawToken::Name => {
+                // As tabelas Unicode do logos podem divergir das usadas em `unicode`, e o
+                // nome reconhecido por ele pode terminar antes ou depois do esperado
+                if !slice.starts_with(is_identifier_start) {
+                    return self.scan_with(start, line, column, Scanner::read_unexpected);
+                }
+                if !slice.chars().all(is_identifier_continue) || self.raw.remainder().starts_with(is_identifier_continue) {
+                    return self.scan_with(start, line, column, Scanner::read_identifier);
+                }
+                let name = normalize_identifier(slice);
+                BorrowedToken::new(lookup_identifier(&name), name, line, column, span)
+            },
+            // Uma string vazia seguida de aspas pode ser o início de aspas triplas não fechadas
+            RawToken::String if self.raw.remainder().starts_with(['"', '\'']) => {
+                return self.scan_with(start, line, column, Scanner::read_string)
+            },
+            RawToken::String => match plain_string(slice) {
+                Some((token_type, value)) => BorrowedToken::new(token_type, value, line, column, span),
+                None => return self.scan_with(start, line, column, Scanner::read_string),
+            },
+            RawToken::StringStart => return self.scan_with(start, line, column, Scanner::read_string),
+            RawToken::Int | RawToken::Float | RawToken::Imaginary => {
+                // Literais incompletos ou seguidos de letras e dígitos, como 0x ou 12abc,
+                // são malformados e o `Scanner` descreve o erro
+                let next = self.raw.remainder().chars().next().unwrap_or('\0');
+                if !is_complete_number(raw, slice) || next.is_alphabetic() || next == '_' || next.is_ascii_digit() {
+                    return self.scan_with(start, line, column, Scanner::read_number);
+                }
+                BorrowedToken::new(raw.token_type().unwrap(), slice, line, column, span)
+            },
+            _ => {
+                match raw {
+                    RawToken::LParen | RawToken::LBracket | RawToken::LBrace => self.bracket_depth += 1,
+                    RawToken::RParen | RawToken::RBracket | RawToken::RBrace => {
+                        self.bracket_depth = self.bracket_depth.saturating_sub(1)
+                    },
+                    _ => {},
+                }
+                BorrowedToken::new(raw.token_type().unwrap(), slice, line, column, span)
+            },
+        };
+
+        self.pending_tokens.push_back(token);
+    }
+
+    // Lê com um `Scanner` o token que começa no byte `start`, na linha e coluna informadas,
+    // e retoma o logos logo depois dele. F-strings geram vários tokens e erros viram um
+    // token Illegal
+    fn scan_with(&mut self, start: usize, line: usize, column: usize, read: fn(&mut Scanner<'src>) -> Result<(), Diagnostic>) {
+        let mut scanner = Scanner::new(self.source, self.tab_policy, self.file_id, start, line, column);
+        scanner.scan(read);
+
+        self.seek(scanner.offset());
+        self.pending_tokens.extend(scanner.tokens);
+        self.diagnostics.append(&mut scanner.diagnostics);
+    }
+
+    // Faz o logos continuar a partir do byte `offset`, que pode estar antes do fim do
+    // último token bruto quando o `Scanner` lê menos do que o logos reconheceu
+    fn seek(&mut self, offset: usize) {
+        let end = self.raw.span().end;
+        if offset >= end {
+            self.raw.bump(offset - end);
+        } else {
+            self.raw = RawToken::lexer(self.source);
+            self.raw.bump(offset);
+        }
+    }
+
+    // Verifica se a linha atual, que termina no '\n' em `end`, não tem conteúdo
+    fn is_blank_line(&self, end: usize) -> bool {
+        let rest = self.source[self.line_start..end].trim_start_matches([' ', '\t']);
+        rest.is_empty() || rest == "\r" || rest.starts_with('#')
+    }
+
+    // Compara a indentação da linha atual com a pilha de níveis e enfileira
+    // INDENT/DEDENT. Erros de indentação são registrados sem interromper a análise
+    fn process_indentation(&mut self) {
+        let text = &self.source[self.line_start..];
+        let content = text.trim_start_matches([' ', '\t']);
+        let start = self.line_start + text.len() - content.len();
+        let (indent, alt_indent) = self.tab_policy.measure(text[..start - self.line_start].chars());
+        let (line, column) = self.position_of(start);
+
+        let change = self.indent_stack.compare(indent, alt_indent);
+        if let Some(code) = change.error {
+            let message = if code == DiagnosticCode::InconsistentDedent {
+                format!(
+                    "Desindentação não corresponde a nenhum nível de indentação externo na linha {} coluna {}",
+                    line, column
+                )
+            } else {
+                format!("Uso inconsistente de tabs e espaços na indentação na linha {} coluna {}", line, column)
+            };
+            let end = start + content.chars().next().map_or(0, char::len_utf8);
+            self.diagnostics.push(Diagnostic::new(code, message, Span::new(self.file_id, start, end)));
+        }
+        self.indent_stack.apply(change, indent, alt_indent);
+
+        let span = Span::new(self.file_id, start, start);
+        for _ in 0..change.dedents {
+            self.pending_tokens.push_back(BorrowedToken::new(TokenType::Dedent, "DEDENT", line, column, span));
+        }
+        if change.indent {
+            self.pending_tokens.push_back(BorrowedToken::new(TokenType::Indent, "INDENT", line, column, span));
+        }
+    }
+
+    // Gera o NEWLINE final, os DEDENTs pendentes e o EOF
+    fn end_of_input(&mut self) {
+        // Uma última linha sem '\n' mas com conteúdo ainda tem a indentação medida
+        let rest = self.source[self.line_start..].trim_start_matches([' ', '\t']);
+        if self.at_line_start && !rest.is_empty() && !rest.starts_with('#') {
+            self.at_line_start = false;
+            self.process_indentation();
+        }
+
+        let offset = self.source.len();
+        let (line, column) = self.position_of(offset);
+        let span = Span::new(self.file_id, offset, offset);
+
+        if self.line_has_content {
+            self.line_has_content = false;
+            self.pending_tokens.push_back(BorrowedToken::new(TokenType::Newline, "\\n", line, column, span));
+        }
+
+        // Fecha todos os níveis abertos, como uma linha sem indentação
+        let change = self.indent_stack.compare(0, 0);
+        for _ in 0..change.dedents {
+            self.pending_tokens.push_back(BorrowedToken::new(TokenType::Dedent, "DEDENT", line, column, span));
+        }
+        self.indent_stack.apply(change, 0, 0);
+
+        self.pending_tokens.push_back(BorrowedToken::new(TokenType::Eof, "", line, column, span));
+    }
+
+    // Linha e coluna do byte `offset`, contadas a partir da última posição calculada
+    fn position_of(&mut self, offset: usize) -> (usize, usize) {
+        for &byte in &self.source.as_bytes()[self.position..offset] {
+            if byte == b'\n' {
+                self.line += 1;
+                self.column = 1;
+            } else if byte & 0xC0 != 0x80 {
+                // Bytes de continuação UTF-8 não iniciam um novo caractere
+                self.column += 1;
+            }
+        }
+        self.position = offset;
+        (self.line, self.column)
+    }
+}
+
+// Valor de uma string que não precisa ser decodificada. Retorna None quando a string
+// precisa ser lida pelo `Scanner`
+fn plain_string(slice: &str) -> Option<(TokenType, &str)> {
+    let quote_index = slice.find(['"', '\''])?;
+    let prefix = &slice[..quote_index];
+    let body = &slice[quote_index..];
+    let quotes = if body.len() >= 6 && (body.starts_with("\"\"\"") || body.starts_with("\'\'\'")) { 3 } else { 1 };
+    let value = &slice[quote_index + quotes..slice.len() - quotes];
+
+    // Em strings raw as barras são mantidas como estão
+    let is_raw = prefix.contains(['r', 'R']);
+    let is_bytes = prefix.contains(['b', 'B']);
+    if value.contains('\r') || (!is_raw && value.contains('\\')) || (is_bytes && !value.is_ascii()) {
+        return None;
+    }
+
+    let token_type = if is_bytes { TokenType::Bytes } else { TokenType::String };
+    Some((token_type, value))
+}
+
+// Verifica se `text` começa com uma string, com ou sem prefixo
+fn starts_string(text: &str) -> bool {
+    let prefix_len = text.find(|ch: char| !ch.is_ascii_alphabetic()).unwrap_or(text.len());
+    let is_prefix = matches!(
+        text[..prefix_len].to_ascii_lowercase().as_str(),
+        "" | "r" | "b" | "rb" | "br" | "f" | "rf" | "fr"
+    );
+    is_prefix && text[prefix_len..].starts_with(['"', '\''])
+}
+
+// Verifica se o literal numérico reconhecido é bem formado. O logos pode devolver um
+// prefixo incompleto, como "0x" ou "1e", quando o padrão falha no meio
+fn is_complete_number(raw: RawToken, slice: &str) -> bool {
+    let last = slice.chars().last().unwrap_or('_');
+
+    match raw {
+        RawToken::Int => {
+            let lower = slice.to_ascii_lowercase();
+            match lower.strip_prefix("0x").or_else(|| lower.strip_prefix("0o")).or_else(|| lower.strip_prefix("0b")) {
+                Some(digits) => !digits.is_empty() && last.is_ascii_hexdigit(),
+                // Inteiros decimais não podem ter zeros à esquerda
+                None => last.is_ascii_digit() && (!slice.starts_with('0') || slice.chars().all(|ch| ch == '0' || ch == '_')),
+            }
+        },
+        RawToken::Float => last.is_ascii_digit() || last == '.',
+        RawToken::Imaginary => {
+            let before = slice[..slice.len() - 1].chars().last().unwrap_or('_');
+            matches!(last, 'j' | 'J') && (before.is_ascii_digit() || before == '.')
+        },
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recovering_tokens(input: &str) -> (Vec<Token>, Vec<Diagnostic>) {
+        let mut lexer = LogosLexer::new(input);
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_token_recovering();
+            let is_eof = token.token_type == TokenType::Eof;
+            tokens.push(token);
+            if is_eof {
+                return (tokens, lexer.take_diagnostics());
+            }
+        }
+    }
+
+    #[test]
+    fn test_scanner_tokens_resume_logos() {
+        let input = "s = 'a\\tb' + f\"{x:>{w}}\" + 0x + 1\nt = 2\n";
+        let (tokens, diagnostics) = recovering_tokens(input);
+
+        let described: Vec<(TokenType, &str, usize, usize)> = tokens
+            .iter()
+            .map(|t| (t.token_type, t.literal.as_str(), t.line, t.column))
+            .collect();
+        assert_eq!(described, vec![
+            (TokenType::Identifier, "s", 1, 1),
+            (TokenType::Assign, "=", 1, 3),
+            (TokenType::String, "a\tb", 1, 5),
+            (TokenType::Plus, "+", 1, 12),
+            (TokenType::FStringStart, "f\"", 1, 14),
+            (TokenType::LBrace, "{", 1, 16),
+            (TokenType::Identifier, "x", 1, 17),
+            (TokenType::Colon, ":", 1, 18),
+            (TokenType::FStringMiddle, ">", 1, 19),
+            (TokenType::LBrace, "{", 1, 20),
+            (TokenType::Identifier, "w", 1, 21),
+            (TokenType::RBrace, "}", 1, 22),
+            (TokenType::RBrace, "}", 1, 23),
+            (TokenType::FStringEnd, "\"", 1, 24),
+            (TokenType::Plus, "+", 1, 26),
+            (TokenType::Illegal, "0x", 1, 28),
+            (TokenType::Plus, "+", 1, 31),
+            (TokenType::Int, "1", 1, 33),
+            (TokenType::Newline, "\\n", 1, 34),
+            (TokenType::Identifier, "t", 2, 1),
+            (TokenType::Assign, "=", 2, 3),
+            (TokenType::Int, "2", 2, 5),
+            (TokenType::Newline, "\\n", 2, 6),
+            (TokenType::Eof, "", 3, 1),
+        ]);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Literal numérico inválido '0x': literal hexadecimal sem dígitos na linha 1 coluna 28");
+    }
+
+    #[test]
+    fn test_borrowed_tokens_reference_source() {
+        use std::borrow::Cow;
+
+        let input = "nome = 'Odin' + \"a\\tb\"\n";
+        let mut lexer = LogosLexer::new(input);
+        let mut tokens = Vec::new();
+        loop {
+            let token = lexer.next_borrowed().unwrap();
+            let is_eof = token.token_type == TokenType::Eof;
+            tokens.push(token);
+            if is_eof {
+                break;
+            }
+        }
+
+        let literals: Vec<(&str, bool)> = tokens
+            .iter()
+            .map(|t| (t.literal.as_ref(), matches!(t.literal, Cow::Borrowed(_))))
+            .collect();
+        assert_eq!(literals, vec![
+            ("nome", true),
+            ("=", true),
+            ("Odin", true),
+            ("+", true),
+            ("a\tb", false), // escapes precisam ser decodificados
+            ("\\n", true),
+            ("", true),
+        ]);
+
+        // Os literais emprestados apontam para o próprio código-fonte
+        let nome = &tokens[0].literal;
+        assert_eq!(nome.as_ptr(), input.as_ptr());
+
+        let owned: Vec<Token> = tokens.into_iter().map(BorrowedToken::into_owned).collect();
+        assert_eq!(owned[2].literal, "Odin");
+        assert_eq!(owned[4].span, Span::new(0, 16, 22));
+    }
+
+    #[test]
+    fn test_string_parts_are_copied_only_when_decoded() {
+        use std::borrow::Cow;
+
+        let input = "f\"Olá {nome!r:>10} e {{x}}\" + r'\\d+' + '''a\r\nb''' + 'é\\x41'\n";
+        let tokens = crate::lexer::tokenize_borrowed(input).unwrap();
+
+        let literals: Vec<(TokenType, &str, bool)> = tokens
+            .iter()
+            .filter(|t| matches!(t.token_type, TokenType::FStringMiddle | TokenType::String))
+            .map(|t| (t.token_type, t.literal.as_ref(), matches!(t.literal, Cow::Borrowed(_))))
+            .collect();
+        assert_eq!(literals, vec![
+            (TokenType::FStringMiddle, "Olá ", true),
+            (TokenType::FStringMiddle, ">10", true),
+            (TokenType::FStringMiddle, " e {x}", false), // chaves duplas viram uma só
+            (TokenType::String, "\\d+", true),
+            (TokenType::String, "a\nb", false), // \r\n vira \n
+            (TokenType::String, "éA", false),
+        ]);
+
+        // Mesmo em strings decodificadas pelo `Scanner`, trechos sem escapes apontam para o código-fonte
+        let middle = &tokens[1].literal;
+        assert_eq!(middle.as_ptr(), input[2..].as_ptr());
+    }
+
+    #[test]
+    fn test_errors_do_not_stop_the_analysis() {
+        let cases = vec![
+            ("x = 0x\ny = 1\n", DiagnosticCode::InvalidNumber),
+            ("x = 12abc + 1\n", DiagnosticCode::InvalidNumber),
+            ("s = 'sem fim\nt = 2\n", DiagnosticCode::UnterminatedString),
+            ("x = 1 \\ 2\n", DiagnosticCode::InvalidContinuation),
+            ("x = \"\"\"sem fim\ny = 1\n", DiagnosticCode::UnterminatedString),
+            ("if a:\n        b\n    c\n", DiagnosticCode::InconsistentDedent),
+            ("if a:\n\tb\n        c\n", DiagnosticCode::TabError),
+            ("f'{}' + f'{x!z}'\n", DiagnosticCode::InvalidFString),
+        ];
+
+        for (input, code) in cases {
+            let (tokens, diagnostics) = recovering_tokens(input);
+            assert_eq!(diagnostics.first().map(|d| d.code), Some(code), "{:?}", input);
+            assert_eq!(tokens.last().unwrap().token_type, TokenType::Eof, "{:?}", input);
+        }
+
+        // Caracteres que não iniciam nenhum token viram Illegal sem gerar erro
+        let (tokens, diagnostics) = recovering_tokens("x = $\n");
+        assert_eq!(tokens[2].token_type, TokenType::Illegal);
+        assert_eq!(tokens[2].literal, "$");
+        assert!(diagnostics.is_empty());
+
+        let mut lexer = LogosLexer::new("a = 1\nb = 0b2\n");
+        let mut error = None;
+        while error.is_none() {
+            match lexer.next_token() {
+                Ok(token) => assert_ne!(token.token_type, TokenType::Eof),
+                Err(err) => error = Some(err),
+            }
+        }
+        assert_eq!(error.unwrap(), "Literal numérico inválido '0b': literal binário sem dígitos na linha 2 coluna 5");
+    }
+
+    #[test]
+    fn test_null_character_ends_input() {
+        let (tokens, diagnostics) = recovering_tokens("x = 1\0 $ 'sem fim");
+
+        let types: Vec<TokenType> = tokens.iter().map(|t| t.token_type).collect();
+        assert_eq!(types, vec![TokenType::Identifier, TokenType::Assign, TokenType::Int, TokenType::Newline, TokenType::Eof]);
+        assert_eq!(tokens[4].span, Span::new(0, 5, 5));
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -1,17 +1,31 @@
 pub mod token;
 pub mod lexer;
+pub mod diagnostic;
+pub mod logos_lexer;
+pub mod trivia;
+pub mod unicode;
 
-pub use self::lexer::Lexer;
-pub use self::token::{Token, TokenType};
+pub use self::lexer::TabPolicy;
+pub use self::token::{BorrowedToken, Span, Token, TokenType};
+pub use self::diagnostic::{Diagnostic, DiagnosticCode, Severity};
+pub use self::logos_lexer::LogosLexer;
+pub use self::trivia::{TokenTrivia, Trivia, TriviaKind};
 
 
 
 pub fn tokenize(input: &str) -> Result<Vec<Token>, String> {
-    let mut lexer = Lexer::new(input);
+    let tokens = tokenize_borrowed(input)?;
+    Ok(tokens.into_iter().map(BorrowedToken::into_owned).collect())
+}
+
+/// Como `tokenize`, mas os literais dos tokens referenciam o código-fonte em vez
+/// de serem copiados
+pub fn tokenize_borrowed(input: &str) -> Result<Vec<BorrowedToken<'_>>, String> {
+    let mut lexer = LogosLexer::new(input);
     let mut tokens = Vec::new();
     
     loop {
-        let token = lexer.next_token()?;
+        let token = lexer.next_borrowed()?;
         let is_eof = token.token_type == TokenType::Eof;
         
         tokens.push(token);
@@ -22,4 +36,40 @@ pub fn tokenize(input: &str) -> Result<Vec<Token>, String> {
     }
     
     Ok(tokens)
-}
\ No newline at end of file
+}
+
+/// Analisa todo o código-fonte sem parar no primeiro erro. Trechos inválidos viram
+/// tokens `Illegal` e cada erro encontrado é devolvido como um diagnóstico, junto com
+/// avisos sobre identificadores que podem ser confundidos
+pub fn tokenize_with_diagnostics(input: &str) -> (Vec<Token>, Vec<Diagnostic>) {
+    let mut lexer = LogosLexer::new(input);
+    let mut tokens = Vec::new();
+    
+    loop {
+        let token = lexer.next_token_recovering();
+        let is_eof = token.token_type == TokenType::Eof;
+        
+        tokens.push(token);
+        
+        if is_eof {
+            break;
+        }
+    }
+    
+    let mut diagnostics = lexer.take_diagnostics();
+    diagnostics.extend(unicode::identifier_warnings(&tokens));
+    (tokens, diagnostics)
+}
+
+/// Como `tokenize_with_diagnostics`, mas também devolve, para cada token, os espaços,
+/// comentários e quebras de linha à sua volta, de modo que `trivia::render`
+/// reconstrói o código-fonte original byte a byte.
+///
+/// As duas listas estão alinhadas por índice: `trivia[i]` pertence a `tokens[i]` e
+/// ambas têm o mesmo tamanho, inclusive quando há erros, já que trechos inválidos
+/// também viram tokens (`Illegal`) e recebem sua própria entrada
+pub fn tokenize_with_trivia(input: &str) -> (Vec<Token>, Vec<TokenTrivia>, Vec<Diagnostic>) {
+    let (tokens, diagnostics) = tokenize_with_diagnostics(input);
+    let trivia = trivia::attach_trivia(input, &tokens);
+    (tokens, trivia, diagnostics)
+}
@@ -1,4 +1,9 @@
-#[derive(Debug, PartialEq, Clone)]
+use std::borrow::Cow;
+use num_bigint::BigInt;
+
+
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum TokenType {
     // Tokens especiais
     Illegal,
@@ -8,7 +13,12 @@ pub enum TokenType {
     Identifier,
     Int,
     Float,
+    Imaginary,   // 2j, 1.5e3J
     String,
+    Bytes,
+    FStringStart,  // f" (prefixo e aspas de abertura)
+    FStringMiddle, // trecho literal ou especificação de formato de uma f-string
+    FStringEnd,    // aspas de fechamento de uma f-string
     
     // Operators
     Assign,      // =
@@ -17,8 +27,18 @@ pub enum TokenType {
     Bang,        // !
     Asterisk,    // *
     Slash,       // /
+    FloorDiv,    // //
     Percent,     // %
     Power,       // **
+    At,          // @
+    
+    // Operadores bit a bit
+    Ampersand,   // &
+    Pipe,        // |
+    Caret,       // ^
+    Tilde,       // ~
+    ShiftLeft,   // <<
+    ShiftRight,  // >>
     
     Eq,          // ==
     NotEq,       // !=
@@ -32,6 +52,16 @@ pub enum TokenType {
     MinusAssign, // -=
     SlashAssign, // /=
     StarAssign,  // *=
+    FloorDivAssign,   // //=
+    PercentAssign,    // %=
+    PowerAssign,      // **=
+    AtAssign,         // @=
+    AmpersandAssign,  // &=
+    PipeAssign,       // |=
+    CaretAssign,      // ^=
+    ShiftLeftAssign,  // <<=
+    ShiftRightAssign, // >>=
+    Walrus,           // :=
     
     // Operadores lógicos
     And,         // and
@@ -43,6 +73,7 @@ pub enum TokenType {
     Semicolon,   // ;
     Colon,       // :
     Dot,         // .
+    Arrow,       // ->
     
     LParen,      // (
     RParen,      // )
@@ -63,6 +94,7 @@ pub enum TokenType {
     While,       // while
     For,         // for
     In,          // in
+    Is,          // is
     Break,       // break
     Continue,    // continue
     None,        // None
@@ -91,6 +123,7 @@ pub struct Token {
     pub literal: String,
     pub line: usize,
     pub column: usize,
+    pub span: Span,
 }
 
 impl Token {
@@ -100,8 +133,75 @@ impl Token {
             literal,
             line,
             column,
+            span: Span::default(),
         }
     }
+    
+    /// Associa ao token o trecho do código-fonte de onde ele foi lido
+    pub fn with_span(mut self, span: Span) -> Self {
+        self.span = span;
+        self
+    }
+}
+
+/// Token que referencia o código-fonte em vez de copiar o literal. O literal só é
+/// alocado quando precisa ser decodificado, como em strings com escapes
+#[derive(Debug, PartialEq, Clone)]
+pub struct BorrowedToken<'src> {
+    pub token_type: TokenType,
+    pub literal: Cow<'src, str>,
+    pub line: usize,
+    pub column: usize,
+    pub span: Span,
+}
+
+impl<'src> BorrowedToken<'src> {
+    pub fn new(token_type: TokenType, literal: impl Into<Cow<'src, str>>, line: usize, column: usize, span: Span) -> Self {
+        BorrowedToken {
+            token_type,
+            literal: literal.into(),
+            line,
+            column,
+            span,
+        }
+    }
+    
+    /// Converte para um `Token`, copiando o literal se ele ainda referencia o código-fonte
+    pub fn into_owned(self) -> Token {
+        Token::new(self.token_type, self.literal.into_owned(), self.line, self.column).with_span(self.span)
+    }
+}
+
+/// Trecho do código-fonte em bytes: `start` é inclusivo e `end` exclusivo.
+/// `file_id` identifica o arquivo quando vários são analisados juntos
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
+pub struct Span {
+    pub file_id: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(file_id: usize, start: usize, end: usize) -> Self {
+        Span { file_id, start, end }
+    }
+    
+    /// Menor trecho que contém os dois spans
+    pub fn merge(self, other: Span) -> Span {
+        Span {
+            file_id: self.file_id,
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
 }
 
 /// Mapeia palavras-chave para seus tipos de token correspondentes
@@ -118,6 +218,7 @@ pub fn lookup_identifier(identifier: &str) -> TokenType {
         "while" => TokenType::While,
         "for" => TokenType::For,
         "in" => TokenType::In,
+        "is" => TokenType::Is,
         "break" => TokenType::Break,
         "continue" => TokenType::Continue,
         "None" => TokenType::None,
@@ -138,4 +239,32 @@ pub fn lookup_identifier(identifier: &str) -> TokenType {
         "not" => TokenType::Not,
         _ => TokenType::Identifier,
     }
-}
\ No newline at end of file
+}
+
+/// Calcula o valor de um literal inteiro como escrito no código (com prefixo de
+/// base e sublinhados). Inteiros não têm limite de tamanho, como no Python
+pub fn int_literal_value(literal: &str) -> Option<BigInt> {
+    let digits = literal.replace('_', "");
+    let lower = digits.to_ascii_lowercase();
+    
+    let (radix, digits) = if let Some(rest) = lower.strip_prefix("0x") {
+        (16, rest)
+    } else if let Some(rest) = lower.strip_prefix("0o") {
+        (8, rest)
+    } else if let Some(rest) = lower.strip_prefix("0b") {
+        (2, rest)
+    } else {
+        (10, lower.as_str())
+    };
+    
+    BigInt::parse_bytes(digits.as_bytes(), radix)
+}
+
+/// Calcula o valor de um literal de ponto flutuante ou imaginário como escrito no código
+pub fn float_literal_value(literal: &str) -> Option<f64> {
+    literal
+        .replace('_', "")
+        .trim_end_matches(['j', 'J'])
+        .parse::<f64>()
+        .ok()
+}
@@ -0,0 +1,203 @@
+use super::token::{Span, Token};
+
+
+
+/// Tipo de um trecho do código-fonte que não gera token
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum TriviaKind {
+    Whitespace,       // espaços, tabs e outros espaços em branco dentro da linha
+    Comment,          // comentário com #, sem a quebra de linha
+    Newline,          // quebra de linha que não gerou NEWLINE, como em linhas em branco
+    LineContinuation, // '\' seguida de quebra de linha
+    Skipped,          // trecho ignorado pelo lexer, como o que vem depois de um '\0'
+}
+
+/// Trecho do código-fonte entre dois tokens, preservado para ferramentas como
+/// formatadores, que precisam reconstruir o código original
+#[derive(Debug, PartialEq, Clone)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+    pub span: Span,
+}
+
+/// Trivia de um token: os trechos antes dele e os que vêm depois até o fim da linha.
+/// Fica em uma tabela à parte, com uma entrada por token, para que tokens comuns não
+/// carreguem listas vazias
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct TokenTrivia {
+    pub leading: Vec<Trivia>,
+    pub trailing: Vec<Trivia>,
+}
+
+/// Associa a cada token os trechos entre ele e os tokens vizinhos. O que vem depois
+/// de um token até o fim da linha é trivia final dele; o restante, incluindo linhas
+/// em branco e comentários em linhas próprias, é trivia inicial do próximo token.
+/// A tabela retornada tem uma entrada por token, na mesma ordem de `tokens`
+pub fn attach_trivia(source: &str, tokens: &[Token]) -> Vec<TokenTrivia> {
+    let mut trivia = vec![TokenTrivia::default(); tokens.len()];
+    let mut cursor = 0;
+    let mut previous: Option<usize> = None;
+
+    for (index, token) in tokens.iter().enumerate() {
+        let span = token.span;
+        if span.start > cursor {
+            let mut pieces = split_trivia(source, cursor, span.start, span.file_id);
+
+            if let Some(previous) = previous {
+                let line_end = pieces
+                    .iter()
+                    .position(|piece| matches!(piece.kind, TriviaKind::Newline | TriviaKind::LineContinuation))
+                    .unwrap_or(pieces.len());
+                trivia[previous].trailing.extend(pieces.drain(..line_end));
+            }
+            trivia[index].leading.extend(pieces);
+        }
+
+        // Tokens sem extensão, como INDENT e DEDENT, não recebem a trivia final
+        if !span.is_empty() {
+            previous = Some(index);
+        }
+        cursor = cursor.max(span.end);
+    }
+
+    // Trechos depois do último token, como o que segue um '\0'
+    if cursor < source.len() {
+        if let (Some(last), Some(last_trivia)) = (tokens.last(), trivia.last_mut()) {
+            let pieces = split_trivia(source, cursor, source.len(), last.span.file_id);
+            last_trivia.trailing.extend(pieces);
+        }
+    }
+
+    trivia
+}
+
+/// Reconstrói o código-fonte a partir dos tokens e da tabela de trivias de
+/// `attach_trivia`. O texto de cada token vem do código-fonte original, já que
+/// literais de strings são decodificados
+pub fn render(source: &str, tokens: &[Token], trivia: &[TokenTrivia]) -> String {
+    let mut out = String::new();
+    let mut cursor = 0;
+
+    for (token, token_trivia) in tokens.iter().zip(trivia) {
+        for piece in &token_trivia.leading {
+            out.push_str(&piece.text);
+        }
+        if token.span.end > cursor {
+            out.push_str(&source[token.span.start.max(cursor)..token.span.end]);
+            cursor = token.span.end;
+        }
+        for piece in &token_trivia.trailing {
+            out.push_str(&piece.text);
+        }
+    }
+
+    out
+}
+
+// Divide o trecho entre `start` e `end` em espaços, comentários e quebras de linha
+fn split_trivia(source: &str, start: usize, end: usize, file_id: usize) -> Vec<Trivia> {
+    let text = &source[start..end];
+    let mut pieces = Vec::new();
+    let mut offset = 0;
+
+    while offset < text.len() {
+        let rest = &text[offset..];
+        let (kind, len) = if rest.starts_with('\n') {
+            (TriviaKind::Newline, 1)
+        } else if rest.starts_with("\r\n") {
+            (TriviaKind::Newline, 2)
+        } else if rest.starts_with("\\\n") {
+            (TriviaKind::LineContinuation, 2)
+        } else if rest.starts_with("\\\r\n") {
+            (TriviaKind::LineContinuation, 3)
+        } else if rest.starts_with('#') {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            (TriviaKind::Comment, rest[..len].strip_suffix('\r').map_or(len, str::len))
+        } else if rest.starts_with(char::is_whitespace) {
+            let mut len = 0;
+            for (index, ch) in rest.char_indices() {
+                if !ch.is_whitespace() || ch == '\n' || rest[index..].starts_with("\r\n") {
+                    break;
+                }
+                len = index + ch.len_utf8();
+            }
+            (TriviaKind::Whitespace, len)
+        } else {
+            // Caracteres que o lexer não chegou a analisar
+            let len = rest.find(['\n', '\r', '#']).filter(|&len| len > 0).unwrap_or(rest.len());
+            (TriviaKind::Skipped, len)
+        };
+
+        pieces.push(Trivia {
+            kind,
+            text: rest[..len].to_string(),
+            span: Span::new(file_id, start + offset, start + offset + len),
+        });
+        offset += len;
+    }
+
+    pieces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{tokenize_with_trivia, TokenType};
+
+    #[test]
+    fn test_trivia_reconstructs_source() {
+        let input = "# cabeçalho\n\nfunc f(a,  b):  # soma\n    return a + \\\n        b\r\n\n\tx = f'{ a }'\n";
+        let (tokens, trivia, _) = tokenize_with_trivia(input);
+
+        assert_eq!(trivia.len(), tokens.len());
+        assert_eq!(render(input, &tokens, &trivia), input);
+
+        // Comentários em linhas próprias e linhas em branco ficam antes do próximo token
+        assert_eq!(tokens[0].token_type, TokenType::Function);
+        let kinds: Vec<TriviaKind> = trivia[0].leading.iter().map(|t| t.kind).collect();
+        assert_eq!(kinds, vec![TriviaKind::Comment, TriviaKind::Newline, TriviaKind::Newline]);
+        assert_eq!(trivia[0].leading[0].text, "# cabeçalho");
+
+        // O comentário no fim da linha fica depois do ':'
+        let colon = tokens.iter().position(|t| t.token_type == TokenType::Colon).unwrap();
+        let texts: Vec<&str> = trivia[colon].trailing.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["  ", "# soma"]);
+
+        // A continuação de linha fica antes do token da linha seguinte
+        let b = tokens.iter().rposition(|t| t.literal == "b").unwrap();
+        let kinds: Vec<TriviaKind> = trivia[b].leading.iter().map(|t| t.kind).collect();
+        assert_eq!(kinds, vec![TriviaKind::LineContinuation, TriviaKind::Whitespace]);
+    }
+
+    #[test]
+    fn test_trivia_stays_aligned_after_errors() {
+        let input = "x = 0x  # hex\ny = $ 'sem fim\nz = 1\n";
+        let (tokens, trivia, diagnostics) = tokenize_with_trivia(input);
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(trivia.len(), tokens.len());
+        assert_eq!(render(input, &tokens, &trivia), input);
+
+        // Os tokens Illegal têm entrada própria e a trivia dos vizinhos não se desloca
+        let illegal: Vec<usize> = tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.token_type == TokenType::Illegal)
+            .map(|(index, _)| index)
+            .collect();
+        assert_eq!(illegal, vec![2, 6, 7]);
+
+        let texts: Vec<&str> = trivia[2].trailing.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["  ", "# hex"]);
+        assert_eq!(tokens[3].token_type, TokenType::Newline);
+        assert_eq!(trivia[5].trailing[0].text, " ");
+        assert_eq!(trivia[6].trailing[0].text, " ");
+        assert!(trivia[7].leading.is_empty() && trivia[7].trailing.is_empty());
+
+        let z = tokens.iter().position(|t| t.literal == "z").unwrap();
+        assert_eq!(z, 9);
+        assert!(trivia[z].leading.is_empty());
+        assert_eq!(trivia[z].trailing[0].text, " ");
+    }
+}
@@ -0,0 +1,107 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use unicode_normalization::{is_nfkc_quick, IsNormalized, UnicodeNormalization};
+use unicode_security::{skeleton, MixedScript};
+use unicode_xid::UnicodeXID;
+use super::diagnostic::{Diagnostic, DiagnosticCode};
+use super::token::{Token, TokenType};
+
+
+
+/// Caracteres que podem iniciar um identificador (XID_Start ou '_')
+pub fn is_identifier_start(ch: char) -> bool {
+    ch == '_' || ch.is_xid_start()
+}
+
+/// Caracteres que podem continuar um identificador (XID_Continue), incluindo dígitos
+/// e marcas combinantes como o acento de "cafe\u{301}"
+pub fn is_identifier_continue(ch: char) -> bool {
+    ch.is_xid_continue()
+}
+
+/// Normaliza um identificador para NFKC, de modo que "café" escrito em NFC ou NFD
+/// seja o mesmo nome. Identificadores ASCII ou já normalizados não são copiados
+pub fn normalize_identifier(identifier: &str) -> Cow<'_, str> {
+    if identifier.is_ascii() || is_nfkc_quick(identifier.chars()) == IsNormalized::Yes {
+        Cow::Borrowed(identifier)
+    } else {
+        Cow::Owned(identifier.nfkc().collect())
+    }
+}
+
+/// Avisos para identificadores que podem enganar quem lê o código: nomes que misturam
+/// sistemas de escrita, como "pаypal" com um 'а' cirílico, e nomes diferentes que
+/// parecem iguais, como "scope" e "ѕсоре". Só identificadores não ASCII são verificados
+pub fn identifier_warnings(tokens: &[Token]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    // Sem identificadores não ASCII não há o que verificar
+    if !tokens.iter().any(|t| t.token_type == TokenType::Identifier && !t.literal.is_ascii()) {
+        return diagnostics;
+    }
+
+    let mut skeletons: HashMap<String, &str> = HashMap::new();
+
+    for token in tokens.iter().filter(|t| t.token_type == TokenType::Identifier) {
+        let name = token.literal.as_str();
+
+        if !name.is_ascii() && !name.is_single_script() {
+            diagnostics.push(Diagnostic::warning(
+                DiagnosticCode::MixedScriptIdentifier,
+                format!(
+                    "Identificador '{}' mistura sistemas de escrita na linha {} coluna {}",
+                    name, token.line, token.column
+                ),
+                token.span,
+            ));
+        }
+
+        let first = *skeletons.entry(skeleton(name).collect()).or_insert(name);
+        if first != name && !(first.is_ascii() && name.is_ascii()) {
+            diagnostics.push(Diagnostic::warning(
+                DiagnosticCode::ConfusableIdentifier,
+                format!(
+                    "Identificador '{}' pode ser confundido com '{}' na linha {} coluna {}",
+                    name, first, token.line, token.column
+                ),
+                token.span,
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{tokenize, tokenize_with_diagnostics, Severity};
+
+    #[test]
+    fn test_identifiers_are_normalized_to_nfkc() {
+        // "café" em NFC e em NFD, e "ﬁm" com a ligadura ﬁ
+        let tokens = tokenize("café = cafe\u{301} + ﬁm\n").unwrap();
+
+        assert_eq!(tokens[0].literal, "café");
+        assert_eq!(tokens[2].token_type, TokenType::Identifier);
+        assert_eq!(tokens[2].literal, "café");
+        assert_eq!(tokens[4].literal, "fim");
+
+        // O span continua apontando para o texto original
+        assert_eq!(tokens[2].span.len(), "cafe\u{301}".len());
+    }
+
+    #[test]
+    fn test_confusable_identifier_warnings() {
+        let input = "scope = 1\nѕсоре = 2\npаypal = 3\nação = scope\n";
+        let (_, diagnostics) = tokenize_with_diagnostics(input);
+
+        let codes: Vec<DiagnosticCode> = diagnostics.iter().map(|d| d.code).collect();
+        assert_eq!(codes, vec![DiagnosticCode::ConfusableIdentifier, DiagnosticCode::MixedScriptIdentifier]);
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+        assert_eq!(diagnostics[0].message, "Identificador 'ѕсоре' pode ser confundido com 'scope' na linha 2 coluna 1");
+
+        // Avisos não impedem a análise
+        assert!(tokenize(input).is_ok());
+    }
+}
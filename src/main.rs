@@ -13,6 +13,8 @@ use std::process;
 
 use clap::{Parser, Subcommand};
 
+use parser::dump::{json_string, Dump};
+
 
 
 #[derive(Parser)]
@@ -45,6 +47,26 @@ enum Commands {
     },
     /// Inicia o REPL (Read-Eval-Print Loop) interativo
     Repl {},
+    /// Exibe os tokens produzidos pelo lexer, com linha e coluna
+    Tokens {
+        /// Caminho para o arquivo Odin a ser analisado
+        #[arg(required = true)]
+        file: String,
+        
+        /// Exibe a saída em JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Exibe a árvore de sintaxe abstrata (AST) produzida pelo parser
+    Ast {
+        /// Caminho para o arquivo Odin a ser analisado
+        #[arg(required = true)]
+        file: String,
+        
+        /// Exibe a saída em JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -67,6 +89,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Some(Commands::Repl {}) | None => {
             run_repl()?;
         }
+        Some(Commands::Tokens { file, json }) => {
+            print_tokens(file, *json)?;
+        }
+        Some(Commands::Ast { file, json }) => {
+            print_ast(file, *json)?;
+        }
     }
 
     Ok(())
@@ -118,6 +146,56 @@ fn run_repl() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+fn print_tokens(path: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Erro ao ler o arquivo '{}': {}", path, e))?;
+    
+    let tokens = lexer::tokenize(&contents)?;
+    
+    if json {
+        let entries: Vec<String> = tokens
+            .iter()
+            .map(|token| {
+                format!(
+                    "  {{\"type\": {}, \"literal\": {}, \"line\": {}, \"column\": {}, \"span\": {{\"file_id\": {}, \"start\": {}, \"end\": {}}}}}",
+                    json_string(&format!("{:?}", token.token_type)),
+                    json_string(&token.literal),
+                    token.line,
+                    token.column,
+                    token.span.file_id,
+                    token.span.start,
+                    token.span.end,
+                )
+            })
+            .collect();
+        println!("[\n{}\n]", entries.join(",\n"));
+    } else {
+        for token in &tokens {
+            let token_type = format!("{:?}", token.token_type);
+            println!("{:>5}:{:<4} {:<16} {}", token.line, token.column, token_type, json_string(&token.literal));
+        }
+    }
+    
+    Ok(())
+}
+
+fn print_ast(path: &str, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Erro ao ler o arquivo '{}': {}", path, e))?;
+    
+    let tokens = lexer::tokenize(&contents)?;
+    let ast = parser::parse(tokens)?;
+    
+    let dump = ast.dump();
+    if json {
+        println!("{}", dump.to_json());
+    } else {
+        print!("{}", dump.to_tree());
+    }
+    
+    Ok(())
+}
+
 // Função auxiliar para executar código Odin
 fn run_code(code: &str) -> Result<(), Box<dyn std::error::Error>> {
     // Tokenização
@@ -1,5 +1,6 @@
 use std::fmt;
-use crate::lexer::Token;
+use num_bigint::BigInt;
+use crate::lexer::{Span, Token};
 
 
 
@@ -7,12 +8,15 @@ use crate::lexer::Token;
 pub trait Node {
     fn token_literal(&self) -> String;
     fn string(&self) -> String;
+    /// Trecho do código-fonte coberto pelo nó
+    fn span(&self) -> Span;
 }
 
 /// Um programa é uma lista de declarações
 #[derive(Debug, Clone)]
 pub struct Program {
     pub statements: Vec<Statement>,
+    pub span: Span,
 }
 
 impl Node for Program {
@@ -31,12 +35,17 @@ impl Node for Program {
         }
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 impl Program {
     pub fn new() -> Self {
         Program {
             statements: Vec::new(),
+            span: Span::default(),
         }
     }
 }
@@ -55,8 +64,12 @@ pub enum Statement {
     Function(FunctionStatement),
     Class(ClassStatement),
     Import(ImportStatement),
+    With(WithStatement),
     Try(TryStatement),
     Pass(PassStatement),
+    Break(BreakStatement),
+    Continue(ContinueStatement),
+    Raise(RaiseStatement),
 }
 
 impl Node for Statement {
@@ -72,8 +85,12 @@ impl Node for Statement {
             Statement::Function(s) => s.token_literal(),
             Statement::Class(s) => s.token_literal(),
             Statement::Import(s) => s.token_literal(),
+            Statement::With(s) => s.token_literal(),
             Statement::Try(s) => s.token_literal(),
             Statement::Pass(s) => s.token_literal(),
+            Statement::Break(s) => s.token_literal(),
+            Statement::Continue(s) => s.token_literal(),
+            Statement::Raise(s) => s.token_literal(),
         }
     }
 
@@ -89,8 +106,33 @@ impl Node for Statement {
             Statement::Function(s) => s.string(),
             Statement::Class(s) => s.string(),
             Statement::Import(s) => s.string(),
+            Statement::With(s) => s.string(),
             Statement::Try(s) => s.string(),
             Statement::Pass(s) => s.string(),
+            Statement::Break(s) => s.string(),
+            Statement::Continue(s) => s.string(),
+            Statement::Raise(s) => s.string(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            Statement::Let(s) => s.span(),
+            Statement::Return(s) => s.span(),
+            Statement::Expression(s) => s.span(),
+            Statement::Block(s) => s.span(),
+            Statement::If(s) => s.span(),
+            Statement::While(s) => s.span(),
+            Statement::For(s) => s.span(),
+            Statement::Function(s) => s.span(),
+            Statement::Class(s) => s.span(),
+            Statement::Import(s) => s.span(),
+            Statement::With(s) => s.span(),
+            Statement::Try(s) => s.span(),
+            Statement::Pass(s) => s.span(),
+            Statement::Break(s) => s.span(),
+            Statement::Continue(s) => s.span(),
+            Statement::Raise(s) => s.span(),
         }
     }
 }
@@ -101,6 +143,7 @@ pub struct LetStatement {
     pub token: Token,
     pub name: Identifier,
     pub value: Option<Expression>,
+    pub span: Span,
 }
 
 impl Node for LetStatement {
@@ -122,6 +165,10 @@ impl Node for LetStatement {
         out.push_str("\n");
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // Return Statement: Declaração de retorno
@@ -129,6 +176,7 @@ impl Node for LetStatement {
 pub struct ReturnStatement {
     pub token: Token,
     pub return_value: Option<Expression>,
+    pub span: Span,
 }
 
 impl Node for ReturnStatement {
@@ -148,6 +196,10 @@ impl Node for ReturnStatement {
         out.push_str("\n");
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // Expression Statement: Uma expressão usada como statement
@@ -155,6 +207,7 @@ impl Node for ReturnStatement {
 pub struct ExpressionStatement {
     pub token: Token,
     pub expression: Option<Expression>,
+    pub span: Span,
 }
 
 impl Node for ExpressionStatement {
@@ -169,6 +222,10 @@ impl Node for ExpressionStatement {
             String::new()
         }
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // Block Statement: Um bloco de código com múltiplos statements
@@ -176,6 +233,7 @@ impl Node for ExpressionStatement {
 pub struct BlockStatement {
     pub token: Token,
     pub statements: Vec<Statement>,
+    pub span: Span,
 }
 
 impl Node for BlockStatement {
@@ -190,6 +248,10 @@ impl Node for BlockStatement {
         }
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // If Statement: Estrutura condicional
@@ -200,6 +262,7 @@ pub struct IfStatement {
     pub consequence: BlockStatement,
     pub alternative: Option<BlockStatement>,
     pub elif_branches: Vec<(Expression, BlockStatement)>,
+    pub span: Span,
 }
 
 impl Node for IfStatement {
@@ -228,6 +291,10 @@ impl Node for IfStatement {
         
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // While Statement: Loop while
@@ -236,6 +303,7 @@ pub struct WhileStatement {
     pub token: Token,
     pub condition: Expression,
     pub body: BlockStatement,
+    pub span: Span,
 }
 
 impl Node for WhileStatement {
@@ -251,15 +319,21 @@ impl Node for WhileStatement {
         out.push_str(&self.body.string());
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // For Statement: Loop for
 #[derive(Debug, Clone)]
 pub struct ForStatement {
     pub token: Token,
-    pub iterator: Expression,
-    pub iterable: Expression,
+    pub iterator: Box<Expression>, // nome, atributo, índice ou tupla de alvos
+    pub iterable: Box<Expression>,
     pub body: BlockStatement,
+    pub alternative: Option<BlockStatement>, // executado se o loop não terminar com break
+    pub span: Span,
 }
 
 impl Node for ForStatement {
@@ -275,8 +349,18 @@ impl Node for ForStatement {
         out.push_str(&self.iterable.string());
         out.push_str(":\n");
         out.push_str(&self.body.string());
+        
+        if let Some(alt) = &self.alternative {
+            out.push_str("else:\n");
+            out.push_str(&alt.string());
+        }
+        
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // Function Statement: Declaração de função
@@ -284,8 +368,10 @@ impl Node for ForStatement {
 pub struct FunctionStatement {
     pub token: Token,
     pub name: Identifier,
-    pub parameters: Vec<Identifier>,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<Expression>, // anotação após ->
     pub body: BlockStatement,
+    pub span: Span,
 }
 
 impl Node for FunctionStatement {
@@ -299,13 +385,22 @@ impl Node for FunctionStatement {
         out.push_str(&self.name.string());
         out.push_str("(");
         
-        let params: Vec<String> = self.parameters.iter().map(|p| p.string()).collect();
-        out.push_str(&params.join(", "));
+        out.push_str(&parameters_string(&self.parameters));
+        out.push(')');
+        
+        if let Some(return_type) = &self.return_type {
+            out.push_str(" -> ");
+            out.push_str(&return_type.string());
+        }
         
-        out.push_str("):\n");
+        out.push_str(":\n");
         out.push_str(&self.body.string());
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // Class Statement: Declaração de classe
@@ -315,6 +410,8 @@ pub struct ClassStatement {
     pub name: Identifier,
     pub parent: Option<Identifier>,
     pub methods: Vec<FunctionStatement>,
+    pub body: Vec<Statement>, // docstring, atributos e demais instruções fora dos métodos
+    pub span: Span,
 }
 
 impl Node for ClassStatement {
@@ -335,6 +432,11 @@ impl Node for ClassStatement {
         
         out.push_str(":\n");
         
+        for stmt in &self.body {
+            out.push_str(&stmt.string());
+            out.push('\n');
+        }
+        
         for method in &self.methods {
             out.push_str(&method.string());
             out.push_str("\n");
@@ -342,15 +444,20 @@ impl Node for ClassStatement {
         
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // Import Statement: Declaração de importação
 #[derive(Debug, Clone)]
 pub struct ImportStatement {
     pub token: Token,
-    pub module: String,
-    pub items: Vec<(String, Option<String>)>, // (item, alias)
+    pub module: String, // com `from`, o módulo de onde os itens vêm; sem, o primeiro módulo importado
+    pub items: Vec<(String, Option<String>)>, // (item, alias); sem `from`, os módulos importados
     pub is_from: bool,
+    pub span: Span,
 }
 
 impl Node for ImportStatement {
@@ -361,37 +468,32 @@ impl Node for ImportStatement {
     fn string(&self) -> String {
         let mut out = String::new();
         
+        let items: Vec<String> = self.items.iter()
+            .map(|(item, alias)| {
+                if let Some(alias_name) = alias {
+                    format!("{} as {}", item, alias_name)
+                } else {
+                    item.clone()
+                }
+            })
+            .collect();
+        
         if self.is_from {
             out.push_str("from ");
             out.push_str(&self.module);
             out.push_str(" import ");
-            
-            let items: Vec<String> = self.items.iter()
-                .map(|(item, alias)| {
-                    if let Some(alias_name) = alias {
-                        format!("{} as {}", item, alias_name)
-                    } else {
-                        item.clone()
-                    }
-                })
-                .collect();
-            
-            out.push_str(&items.join(", "));
         } else {
             out.push_str("import ");
-            out.push_str(&self.module);
-            
-            if let Some((item, alias)) = self.items.first() {
-                if let Some(alias_name) = alias {
-                    out.push_str(" as ");
-                    out.push_str(alias_name);
-                }
-            }
         }
         
+        out.push_str(&items.join(", "));
         out.push_str("\n");
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // Try Statement: Estrutura try-except
@@ -399,8 +501,9 @@ impl Node for ImportStatement {
 pub struct TryStatement {
     pub token: Token,
     pub try_block: BlockStatement,
-    pub except_blocks: Vec<(Option<Expression>, BlockStatement)>,
+    pub except_blocks: Vec<ExceptClause>,
     pub finally_block: Option<BlockStatement>,
+    pub span: Span,
 }
 
 impl Node for TryStatement {
@@ -413,14 +516,8 @@ impl Node for TryStatement {
         out.push_str("try:\n");
         out.push_str(&self.try_block.string());
         
-        for (exception, block) in &self.except_blocks {
-            out.push_str("except");
-            if let Some(ex) = exception {
-                out.push_str(" ");
-                out.push_str(&ex.string());
-            }
-            out.push_str(":\n");
-            out.push_str(&block.string());
+        for except in &self.except_blocks {
+            out.push_str(&except.string());
         }
         
         if let Some(finally) = &self.finally_block {
@@ -430,12 +527,92 @@ impl Node for TryStatement {
         
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// Except Clause: Tratador de exceção de um try (except Tipo as nome:)
+#[derive(Debug, Clone)]
+pub struct ExceptClause {
+    pub token: Token,
+    pub exception: Option<Expression>, // None em um `except:` sem tipo
+    pub name: Option<Identifier>,      // nome ligado à exceção com `as`
+    pub body: BlockStatement,
+    pub span: Span,
+}
+
+impl Node for ExceptClause {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let mut out = String::new();
+        out.push_str("except");
+        
+        if let Some(exception) = &self.exception {
+            out.push(' ');
+            out.push_str(&exception.string());
+        }
+        
+        if let Some(name) = &self.name {
+            out.push_str(" as ");
+            out.push_str(&name.string());
+        }
+        
+        out.push_str(":\n");
+        out.push_str(&self.body.string());
+        out
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// With Statement: Gerenciadores de contexto (with open(p) as f, lock:)
+#[derive(Debug, Clone)]
+pub struct WithStatement {
+    pub token: Token,
+    pub items: Vec<(Expression, Option<Expression>)>, // (gerenciador, alvo do `as`)
+    pub body: BlockStatement,
+    pub span: Span,
+}
+
+impl Node for WithStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let mut out = String::new();
+        out.push_str("with ");
+        
+        let items: Vec<String> = self.items.iter()
+            .map(|(context, target)| match target {
+                Some(target) => format!("{} as {}", context.string(), target.string()),
+                None => context.string(),
+            })
+            .collect();
+        
+        out.push_str(&items.join(", "));
+        out.push_str(":\n");
+        out.push_str(&self.body.string());
+        out
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // Pass Statement: Declaração pass (nenhuma operação)
 #[derive(Debug, Clone)]
 pub struct PassStatement {
     pub token: Token,
+    pub span: Span,
 }
 
 impl Node for PassStatement {
@@ -446,6 +623,89 @@ impl Node for PassStatement {
     fn string(&self) -> String {
         String::from("pass\n")
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// Break Statement: Interrompe o loop mais interno
+#[derive(Debug, Clone)]
+pub struct BreakStatement {
+    pub token: Token,
+    pub span: Span,
+}
+
+impl Node for BreakStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        String::from("break\n")
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// Continue Statement: Passa para a próxima iteração do loop mais interno
+#[derive(Debug, Clone)]
+pub struct ContinueStatement {
+    pub token: Token,
+    pub span: Span,
+}
+
+impl Node for ContinueStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        String::from("continue\n")
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// Raise Statement: Lança uma exceção (raise Erro("msg") from causa)
+#[derive(Debug, Clone)]
+pub struct RaiseStatement {
+    pub token: Token,
+    pub exception: Option<Expression>, // None relança a exceção sendo tratada
+    pub cause: Option<Expression>,     // exceção indicada com `from`
+    pub span: Span,
+}
+
+impl Node for RaiseStatement {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let mut out = String::new();
+        out.push_str("raise");
+        
+        if let Some(exception) = &self.exception {
+            out.push(' ');
+            out.push_str(&exception.string());
+        }
+        
+        if let Some(cause) = &self.cause {
+            out.push_str(" from ");
+            out.push_str(&cause.string());
+        }
+        
+        out.push('\n');
+        out
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // ===== Expressions =====
@@ -455,20 +715,33 @@ pub enum Expression {
     Identifier(Identifier),
     IntegerLiteral(IntegerLiteral),
     FloatLiteral(FloatLiteral),
+    ImaginaryLiteral(ImaginaryLiteral),
     StringLiteral(StringLiteral),
+    BytesLiteral(BytesLiteral),
+    FString(FStringLiteral),
     Boolean(Boolean),
     None(NoneLiteral),
     Prefix(PrefixExpression),
     Infix(InfixExpression),
+    Logical(LogicalExpression),
+    Comparison(ComparisonExpression),
     If(IfExpression),
     FunctionLiteral(FunctionLiteral),
     Call(CallExpression),
     Index(IndexExpression),
+    Slice(SliceExpression),
     Array(ArrayLiteral),
+    Tuple(TupleLiteral),
+    Starred(StarredExpression),
     Dict(DictLiteral),
+    ListComprehension(ListComprehension),
+    SetComprehension(SetComprehension),
+    DictComprehension(DictComprehension),
+    Generator(GeneratorExpression),
     Attribute(AttributeExpression),
     Assignment(AssignmentExpression),
     Lambda(LambdaExpression),
+    Yield(YieldExpression),
 }
 
 impl Node for Expression {
@@ -477,20 +750,33 @@ impl Node for Expression {
             Expression::Identifier(e) => e.token_literal(),
             Expression::IntegerLiteral(e) => e.token_literal(),
             Expression::FloatLiteral(e) => e.token_literal(),
+            Expression::ImaginaryLiteral(e) => e.token_literal(),
             Expression::StringLiteral(e) => e.token_literal(),
+            Expression::BytesLiteral(e) => e.token_literal(),
+            Expression::FString(e) => e.token_literal(),
             Expression::Boolean(e) => e.token_literal(),
             Expression::None(e) => e.token_literal(),
             Expression::Prefix(e) => e.token_literal(),
             Expression::Infix(e) => e.token_literal(),
+            Expression::Logical(e) => e.token_literal(),
+            Expression::Comparison(e) => e.token_literal(),
             Expression::If(e) => e.token_literal(),
             Expression::FunctionLiteral(e) => e.token_literal(),
             Expression::Call(e) => e.token_literal(),
             Expression::Index(e) => e.token_literal(),
+            Expression::Slice(e) => e.token_literal(),
             Expression::Array(e) => e.token_literal(),
+            Expression::Tuple(e) => e.token_literal(),
+            Expression::Starred(e) => e.token_literal(),
             Expression::Dict(e) => e.token_literal(),
+            Expression::ListComprehension(e) => e.token_literal(),
+            Expression::SetComprehension(e) => e.token_literal(),
+            Expression::DictComprehension(e) => e.token_literal(),
+            Expression::Generator(e) => e.token_literal(),
             Expression::Attribute(e) => e.token_literal(),
             Expression::Assignment(e) => e.token_literal(),
             Expression::Lambda(e) => e.token_literal(),
+            Expression::Yield(e) => e.token_literal(),
         }
     }
 
@@ -499,20 +785,68 @@ impl Node for Expression {
             Expression::Identifier(e) => e.string(),
             Expression::IntegerLiteral(e) => e.string(),
             Expression::FloatLiteral(e) => e.string(),
+            Expression::ImaginaryLiteral(e) => e.string(),
             Expression::StringLiteral(e) => e.string(),
+            Expression::BytesLiteral(e) => e.string(),
+            Expression::FString(e) => e.string(),
             Expression::Boolean(e) => e.string(),
             Expression::None(e) => e.string(),
             Expression::Prefix(e) => e.string(),
             Expression::Infix(e) => e.string(),
+            Expression::Logical(e) => e.string(),
+            Expression::Comparison(e) => e.string(),
             Expression::If(e) => e.string(),
             Expression::FunctionLiteral(e) => e.string(),
             Expression::Call(e) => e.string(),
             Expression::Index(e) => e.string(),
+            Expression::Slice(e) => e.string(),
             Expression::Array(e) => e.string(),
+            Expression::Tuple(e) => e.string(),
+            Expression::Starred(e) => e.string(),
             Expression::Dict(e) => e.string(),
+            Expression::ListComprehension(e) => e.string(),
+            Expression::SetComprehension(e) => e.string(),
+            Expression::DictComprehension(e) => e.string(),
+            Expression::Generator(e) => e.string(),
             Expression::Attribute(e) => e.string(),
             Expression::Assignment(e) => e.string(),
             Expression::Lambda(e) => e.string(),
+            Expression::Yield(e) => e.string(),
+        }
+    }
+
+    fn span(&self) -> Span {
+        match self {
+            Expression::Identifier(e) => e.span(),
+            Expression::IntegerLiteral(e) => e.span(),
+            Expression::FloatLiteral(e) => e.span(),
+            Expression::ImaginaryLiteral(e) => e.span(),
+            Expression::StringLiteral(e) => e.span(),
+            Expression::BytesLiteral(e) => e.span(),
+            Expression::FString(e) => e.span(),
+            Expression::Boolean(e) => e.span(),
+            Expression::None(e) => e.span(),
+            Expression::Prefix(e) => e.span(),
+            Expression::Infix(e) => e.span(),
+            Expression::Logical(e) => e.span(),
+            Expression::Comparison(e) => e.span(),
+            Expression::If(e) => e.span(),
+            Expression::FunctionLiteral(e) => e.span(),
+            Expression::Call(e) => e.span(),
+            Expression::Index(e) => e.span(),
+            Expression::Slice(e) => e.span(),
+            Expression::Array(e) => e.span(),
+            Expression::Tuple(e) => e.span(),
+            Expression::Starred(e) => e.span(),
+            Expression::Dict(e) => e.span(),
+            Expression::ListComprehension(e) => e.span(),
+            Expression::SetComprehension(e) => e.span(),
+            Expression::DictComprehension(e) => e.span(),
+            Expression::Generator(e) => e.span(),
+            Expression::Attribute(e) => e.span(),
+            Expression::Assignment(e) => e.span(),
+            Expression::Lambda(e) => e.span(),
+            Expression::Yield(e) => e.span(),
         }
     }
 }
@@ -522,6 +856,7 @@ impl Node for Expression {
 pub struct Identifier {
     pub token: Token,
     pub value: String,
+    pub span: Span,
 }
 
 impl Node for Identifier {
@@ -532,13 +867,19 @@ impl Node for Identifier {
     fn string(&self) -> String {
         self.value.clone()
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
-// IntegerLiteral: Literal inteiro
+// IntegerLiteral: Literal inteiro, sem limite de tamanho. A árvore ainda não tem um
+// avaliador; quem avaliar `2 ** 100` deve operar sobre o BigInt em vez de um i64
 #[derive(Debug, Clone)]
 pub struct IntegerLiteral {
     pub token: Token,
-    pub value: i64,
+    pub value: BigInt,
+    pub span: Span,
 }
 
 impl Node for IntegerLiteral {
@@ -549,6 +890,10 @@ impl Node for IntegerLiteral {
     fn string(&self) -> String {
         self.token.literal.clone()
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // FloatLiteral: Literal de ponto flutuante
@@ -556,6 +901,7 @@ impl Node for IntegerLiteral {
 pub struct FloatLiteral {
     pub token: Token,
     pub value: f64,
+    pub span: Span,
 }
 
 impl Node for FloatLiteral {
@@ -566,6 +912,32 @@ impl Node for FloatLiteral {
     fn string(&self) -> String {
         self.token.literal.clone()
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// ImaginaryLiteral: Literal de número imaginário (ex: 2j)
+#[derive(Debug, Clone)]
+pub struct ImaginaryLiteral {
+    pub token: Token,
+    pub value: f64,
+    pub span: Span,
+}
+
+impl Node for ImaginaryLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // StringLiteral: Literal de string
@@ -573,6 +945,7 @@ impl Node for FloatLiteral {
 pub struct StringLiteral {
     pub token: Token,
     pub value: String,
+    pub span: Span,
 }
 
 impl Node for StringLiteral {
@@ -581,8 +954,132 @@ impl Node for StringLiteral {
     }
 
     fn string(&self) -> String {
-        format!("\"{}\"", self.value)
+        format!("\"{}\"", escape_string(&self.value))
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// BytesLiteral: Literal de bytes (b"...")
+#[derive(Debug, Clone)]
+pub struct BytesLiteral {
+    pub token: Token,
+    pub value: Vec<u8>,
+    pub span: Span,
+}
+
+impl Node for BytesLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let mut out = String::from("b\"");
+        for byte in &self.value {
+            match byte {
+                b'"' => out.push_str("\\\""),
+                b'\\' => out.push_str("\\\\"),
+                b'\n' => out.push_str("\\n"),
+                b'\r' => out.push_str("\\r"),
+                b'\t' => out.push_str("\\t"),
+                0x20..=0x7E => out.push(*byte as char),
+                _ => out.push_str(&format!("\\x{:02x}", byte)),
+            }
+        }
+        out.push('"');
+        out
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// FStringLiteral: String formatada (f"texto {expressão!conversão:formato}")
+#[derive(Debug, Clone)]
+pub struct FStringLiteral {
+    pub token: Token,
+    pub parts: Vec<FStringPart>,
+    pub span: Span,
+}
+
+// Trecho de uma f-string: texto literal ou campo de substituição. A especificação de
+// formato também é formada por trechos, já que pode ter campos aninhados: {x:>{largura}}
+#[derive(Debug, Clone)]
+pub enum FStringPart {
+    Literal(String),
+    Field {
+        expression: Box<Expression>,
+        conversion: Option<char>,
+        format_spec: Vec<FStringPart>,
+    },
+}
+
+impl FStringPart {
+    fn write(&self, out: &mut String, in_format_spec: bool) {
+        match self {
+            // O texto da especificação de formato é escrito como está
+            FStringPart::Literal(text) if in_format_spec => out.push_str(text),
+            FStringPart::Literal(text) => {
+                out.push_str(&escape_string(text).replace('{', "{{").replace('}', "}}"));
+            }
+            FStringPart::Field { expression, conversion, format_spec } => {
+                out.push('{');
+                out.push_str(&expression.string());
+                if let Some(conversion) = conversion {
+                    out.push('!');
+                    out.push(*conversion);
+                }
+                if !format_spec.is_empty() {
+                    out.push(':');
+                    for part in format_spec {
+                        part.write(out, true);
+                    }
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+impl Node for FStringLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let mut out = String::from("f\"");
+        
+        for part in &self.parts {
+            part.write(&mut out, false);
+        }
+        
+        out.push('"');
+        out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// Escapa uma string para que ela possa ser escrita de volta entre aspas duplas
+fn escape_string(value: &str) -> String {
+    let mut out = String::new();
+    for ch in value.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if ch.is_control() => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+    out
 }
 
 // Boolean: Valor booleano
@@ -590,6 +1087,7 @@ impl Node for StringLiteral {
 pub struct Boolean {
     pub token: Token,
     pub value: bool,
+    pub span: Span,
 }
 
 impl Node for Boolean {
@@ -600,12 +1098,17 @@ impl Node for Boolean {
     fn string(&self) -> String {
         self.token.literal.clone()
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // NoneLiteral: Valor None
 #[derive(Debug, Clone)]
 pub struct NoneLiteral {
     pub token: Token,
+    pub span: Span,
 }
 
 impl Node for NoneLiteral {
@@ -616,14 +1119,19 @@ impl Node for NoneLiteral {
     fn string(&self) -> String {
         String::from("None")
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
-// PrefixExpression: Expressão prefixada (ex: -5, !true)
+// PrefixExpression: Expressão prefixada (ex: -5, !true, not x)
 #[derive(Debug, Clone)]
 pub struct PrefixExpression {
     pub token: Token,
     pub operator: String,
     pub right: Box<Expression>,
+    pub span: Span,
 }
 
 impl Node for PrefixExpression {
@@ -632,7 +1140,16 @@ impl Node for PrefixExpression {
     }
 
     fn string(&self) -> String {
-        format!("({}{})", self.operator, self.right.string())
+        // Operadores escritos como palavra, como `not`, precisam de espaço
+        if self.operator.starts_with(|ch: char| ch.is_alphabetic()) {
+            format!("({} {})", self.operator, self.right.string())
+        } else {
+            format!("({}{})", self.operator, self.right.string())
+        }
+    }
+
+    fn span(&self) -> Span {
+        self.span
     }
 }
 
@@ -643,6 +1160,7 @@ pub struct InfixExpression {
     pub left: Box<Expression>,
     pub operator: String,
     pub right: Box<Expression>,
+    pub span: Span,
 }
 
 impl Node for InfixExpression {
@@ -658,44 +1176,121 @@ impl Node for InfixExpression {
             self.right.string()
         )
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
-// IfExpression: Expressão condicional
+// LogicalExpression: `and` e `or`, avaliados com curto-circuito: o lado direito só é
+// avaliado se o esquerdo não decidir o resultado, que é o valor do último lado avaliado
 #[derive(Debug, Clone)]
-pub struct IfExpression {
+pub struct LogicalExpression {
     pub token: Token,
-    pub condition: Box<Expression>,
-    pub consequence: BlockStatement,
-    pub alternative: Option<BlockStatement>,
+    pub left: Box<Expression>,
+    pub operator: String, // "and" ou "or"
+    pub right: Box<Expression>,
+    pub span: Span,
 }
 
-impl Node for IfExpression {
+impl Node for LogicalExpression {
     fn token_literal(&self) -> String {
         self.token.literal.clone()
     }
 
     fn string(&self) -> String {
-        let mut out = String::new();
-        out.push_str("if");
-        out.push_str(&self.condition.string());
-        out.push_str(" ");
-        out.push_str(&self.consequence.string());
-        
-        if let Some(alt) = &self.alternative {
-            out.push_str("else ");
+        format!(
+            "({} {} {})",
+            self.left.string(),
+            self.operator,
+            self.right.string()
+        )
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// ComparisonExpression: Comparações encadeadas, como 0 <= x < 10. Equivale a
+// (0 <= x) and (x < 10), mas cada operando é avaliado uma única vez
+#[derive(Debug, Clone)]
+pub struct ComparisonExpression {
+    pub token: Token,
+    pub left: Box<Expression>,
+    pub comparisons: Vec<(String, Expression)>, // (operador, operando à direita), como ("not in", xs)
+    pub span: Span,
+}
+
+impl Node for ComparisonExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let mut out = String::new();
+        out.push('(');
+        out.push_str(&self.left.string());
+        
+        for (operator, right) in &self.comparisons {
+            out.push(' ');
+            out.push_str(operator);
+            out.push(' ');
+            out.push_str(&right.string());
+        }
+        
+        out.push(')');
+        out
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// IfExpression: Expressão condicional
+#[derive(Debug, Clone)]
+pub struct IfExpression {
+    pub token: Token,
+    pub condition: Box<Expression>,
+    pub consequence: BlockStatement,
+    pub alternative: Option<BlockStatement>,
+    pub span: Span,
+}
+
+impl Node for IfExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let mut out = String::new();
+        out.push_str("if");
+        out.push_str(&self.condition.string());
+        out.push_str(" ");
+        out.push_str(&self.consequence.string());
+        
+        if let Some(alt) = &self.alternative {
+            out.push_str("else ");
             out.push_str(&alt.string());
         }
         
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // FunctionLiteral: Literal de função
 #[derive(Debug, Clone)]
 pub struct FunctionLiteral {
     pub token: Token,
-    pub parameters: Vec<Identifier>,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<Box<Expression>>, // anotação após ->
     pub body: BlockStatement,
+    pub span: Span,
 }
 
 impl Node for FunctionLiteral {
@@ -708,14 +1303,128 @@ impl Node for FunctionLiteral {
         out.push_str(&self.token_literal());
         out.push_str("(");
         
-        let params: Vec<String> = self.parameters.iter().map(|p| p.string()).collect();
-        out.push_str(&params.join(", "));
+        out.push_str(&parameters_string(&self.parameters));
+        out.push(')');
+        
+        if let Some(return_type) = &self.return_type {
+            out.push_str(" -> ");
+            out.push_str(&return_type.string());
+        }
         
-        out.push_str(") ");
+        out.push(' ');
         out.push_str(&self.body.string());
         
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// ParameterKind: Forma como um parâmetro recebe seu valor na chamada
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterKind {
+    Positional,    // a ou a=1: por posição ou por nome
+    VarPositional, // *args: posições que sobram
+    KeywordOnly,   // parâmetros depois de *args ou de um * isolado: só por nome
+    VarKeyword,    // **kwargs: nomes que sobram
+}
+
+// Parameter: Parâmetro de uma função ou lambda, com valor padrão opcional
+#[derive(Debug, Clone)]
+pub struct Parameter {
+    pub token: Token,
+    pub name: Identifier,
+    pub kind: ParameterKind,
+    pub default: Option<Expression>,
+    pub span: Span,
+}
+
+impl Node for Parameter {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let mut out = String::new();
+        
+        match self.kind {
+            ParameterKind::VarPositional => out.push('*'),
+            ParameterKind::VarKeyword => out.push_str("**"),
+            ParameterKind::Positional | ParameterKind::KeywordOnly => {}
+        }
+        out.push_str(&self.name.string());
+        
+        if let Some(default) = &self.default {
+            out.push('=');
+            out.push_str(&default.string());
+        }
+        
+        out
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// Lista de parâmetros separados por vírgula. Parâmetros só por nome sem um *args
+// antes deles vêm depois de um * isolado
+fn parameters_string(parameters: &[Parameter]) -> String {
+    let mut params = Vec::new();
+    let mut var_positional = false;
+    
+    for parameter in parameters {
+        match parameter.kind {
+            ParameterKind::VarPositional => var_positional = true,
+            ParameterKind::KeywordOnly if !var_positional => {
+                params.push(String::from("*"));
+                var_positional = true;
+            }
+            _ => {}
+        }
+        params.push(parameter.string());
+    }
+    
+    params.join(", ")
+}
+
+// ArgumentKind: Forma de um argumento na chamada
+#[derive(Debug, Clone)]
+pub enum ArgumentKind {
+    Positional,          // f(x)
+    Keyword(Identifier), // f(c=2)
+    Unpack,              // f(*xs)
+    UnpackKeywords,      // f(**opts)
+}
+
+// Argument: Argumento de uma chamada de função
+#[derive(Debug, Clone)]
+pub struct Argument {
+    pub token: Token,
+    pub kind: ArgumentKind,
+    pub value: Expression,
+    pub span: Span,
+}
+
+impl Node for Argument {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        match &self.kind {
+            ArgumentKind::Positional => self.value.string(),
+            ArgumentKind::Keyword(name) => format!("{}={}", name.string(), self.value.string()),
+            ArgumentKind::Unpack => format!("*{}", self.value.string()),
+            ArgumentKind::UnpackKeywords => format!("**{}", self.value.string()),
+        }
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // CallExpression: Chamada de função
@@ -723,7 +1432,8 @@ impl Node for FunctionLiteral {
 pub struct CallExpression {
     pub token: Token,
     pub function: Box<Expression>,
-    pub arguments: Vec<Expression>,
+    pub arguments: Vec<Argument>,
+    pub span: Span,
 }
 
 impl Node for CallExpression {
@@ -742,14 +1452,20 @@ impl Node for CallExpression {
         out.push_str(")");
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
-// IndexExpression: Acesso de índice (array[index] ou dict[key])
+// IndexExpression: Acesso de índice (array[index] ou dict[key]). O índice pode ser
+// uma fatia (xs[1:3]) ou uma tupla de índices e fatias (m[i, j], m[1:, ::2])
 #[derive(Debug, Clone)]
 pub struct IndexExpression {
     pub token: Token,
     pub left: Box<Expression>,
     pub index: Box<Expression>,
+    pub span: Span,
 }
 
 impl Node for IndexExpression {
@@ -762,10 +1478,64 @@ impl Node for IndexExpression {
         out.push_str("(");
         out.push_str(&self.left.string());
         out.push_str("[");
-        out.push_str(&self.index.string());
+        
+        // Tuplas de índices são escritas sem parênteses, já que podem conter fatias
+        match self.index.as_ref() {
+            Expression::Tuple(tuple) if tuple.elements.len() > 1 => {
+                let elements: Vec<String> = tuple.elements.iter().map(|e| e.string()).collect();
+                out.push_str(&elements.join(", "));
+            }
+            index => out.push_str(&index.string()),
+        }
+        
         out.push_str("])");
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// SliceExpression: Fatia dentro de um índice (início:fim:passo), com as três partes opcionais
+#[derive(Debug, Clone)]
+pub struct SliceExpression {
+    pub token: Token,
+    pub start: Option<Box<Expression>>,
+    pub stop: Option<Box<Expression>>,
+    pub step: Option<Box<Expression>>,
+    pub span: Span,
+}
+
+impl Node for SliceExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let mut out = String::new();
+        
+        if let Some(start) = &self.start {
+            out.push_str(&start.string());
+        }
+        
+        out.push(':');
+        
+        if let Some(stop) = &self.stop {
+            out.push_str(&stop.string());
+        }
+        
+        if let Some(step) = &self.step {
+            out.push(':');
+            out.push_str(&step.string());
+        }
+        
+        out
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // ArrayLiteral: Literal de array
@@ -773,6 +1543,7 @@ impl Node for IndexExpression {
 pub struct ArrayLiteral {
     pub token: Token,
     pub elements: Vec<Expression>,
+    pub span: Span,
 }
 
 impl Node for ArrayLiteral {
@@ -790,6 +1561,63 @@ impl Node for ArrayLiteral {
         out.push_str("]");
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// TupleLiteral: Tupla de expressões, com ou sem parênteses: (1, 2), (1,), () ou os
+// alvos em `a, b = b, a` e `for chave, valor in pares`
+#[derive(Debug, Clone)]
+pub struct TupleLiteral {
+    pub token: Token,
+    pub elements: Vec<Expression>,
+    pub span: Span,
+}
+
+impl Node for TupleLiteral {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let elements: Vec<String> = self.elements.iter().map(|e| e.string()).collect();
+        
+        // Uma tupla de um elemento precisa da vírgula: (x,)
+        if elements.len() == 1 {
+            format!("({},)", elements[0])
+        } else {
+            format!("({})", elements.join(", "))
+        }
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// StarredExpression: Expressão desempacotada com *, como o alvo em `primeiro, *resto = itens`
+// ou os elementos em [*a, *b]
+#[derive(Debug, Clone)]
+pub struct StarredExpression {
+    pub token: Token,
+    pub value: Box<Expression>,
+    pub span: Span,
+}
+
+impl Node for StarredExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        format!("*{}", self.value.string())
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // DictLiteral: Literal de dicionário
@@ -797,6 +1625,7 @@ impl Node for ArrayLiteral {
 pub struct DictLiteral {
     pub token: Token,
     pub pairs: Vec<(Expression, Expression)>,
+    pub span: Span,
 }
 
 impl Node for DictLiteral {
@@ -818,6 +1647,183 @@ impl Node for DictLiteral {
         out.push_str("}");
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// ComprehensionClause: Cláusula `for alvo in iterável if condição...` de uma compreensão.
+// Os nomes ligados pelos alvos ficam no `ComprehensionScope` da compreensão
+#[derive(Debug, Clone)]
+pub struct ComprehensionClause {
+    pub token: Token,
+    pub target: Expression,
+    pub iterable: Expression,
+    pub conditions: Vec<Expression>,
+    pub span: Span,
+}
+
+impl Node for ComprehensionClause {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let mut out = String::new();
+        out.push_str("for ");
+        out.push_str(&self.target.string());
+        out.push_str(" in ");
+        out.push_str(&self.iterable.string());
+        
+        for condition in &self.conditions {
+            out.push_str(" if ");
+            out.push_str(&condition.string());
+        }
+        
+        out
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// ComprehensionScope: Escopo próprio de uma compreensão. `locals` são os nomes ligados
+// pelos alvos das cláusulas, na ordem em que aparecem, e não alteram variáveis de mesmo
+// nome fora dela. Só o iterável da primeira cláusula é avaliado no escopo de fora; o
+// elemento, os filtros e os demais iteráveis enxergam `locals`
+#[derive(Debug, Clone, Default)]
+pub struct ComprehensionScope {
+    pub locals: Vec<Identifier>,
+}
+
+impl ComprehensionScope {
+    pub fn new(clauses: &[ComprehensionClause]) -> Self {
+        let mut scope = ComprehensionScope::default();
+        for clause in clauses {
+            scope.bind(&clause.target);
+        }
+        scope
+    }
+
+    pub fn is_local(&self, name: &str) -> bool {
+        self.locals.iter().any(|local| local.value == name)
+    }
+
+    // Atributos e índices como alvo alteram objetos já existentes e não ligam nomes
+    fn bind(&mut self, target: &Expression) {
+        match target {
+            Expression::Identifier(ident) if !self.is_local(&ident.value) => {
+                self.locals.push(ident.clone());
+            }
+            Expression::Tuple(tuple) => tuple.elements.iter().for_each(|e| self.bind(e)),
+            Expression::Array(array) => array.elements.iter().for_each(|e| self.bind(e)),
+            Expression::Starred(starred) => self.bind(&starred.value),
+            _ => {}
+        }
+    }
+}
+
+// ListComprehension: Compreensão de lista ([x * 2 for x in xs if x > 0])
+#[derive(Debug, Clone)]
+pub struct ListComprehension {
+    pub token: Token,
+    pub element: Box<Expression>,
+    pub clauses: Vec<ComprehensionClause>,
+    pub scope: ComprehensionScope,
+    pub span: Span,
+}
+
+impl Node for ListComprehension {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let clauses: Vec<String> = self.clauses.iter().map(|c| c.string()).collect();
+        format!("[{} {}]", self.element.string(), clauses.join(" "))
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// SetComprehension: Compreensão de conjunto ({x for x in xs})
+#[derive(Debug, Clone)]
+pub struct SetComprehension {
+    pub token: Token,
+    pub element: Box<Expression>,
+    pub clauses: Vec<ComprehensionClause>,
+    pub scope: ComprehensionScope,
+    pub span: Span,
+}
+
+impl Node for SetComprehension {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let clauses: Vec<String> = self.clauses.iter().map(|c| c.string()).collect();
+        format!("{{{} {}}}", self.element.string(), clauses.join(" "))
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// DictComprehension: Compreensão de dicionário ({k: v for k, v in pares})
+#[derive(Debug, Clone)]
+pub struct DictComprehension {
+    pub token: Token,
+    pub key: Box<Expression>,
+    pub value: Box<Expression>,
+    pub clauses: Vec<ComprehensionClause>,
+    pub scope: ComprehensionScope,
+    pub span: Span,
+}
+
+impl Node for DictComprehension {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let clauses: Vec<String> = self.clauses.iter().map(|c| c.string()).collect();
+        format!("{{{}: {} {}}}", self.key.string(), self.value.string(), clauses.join(" "))
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// GeneratorExpression: Gerador avaliado sob demanda ((x for x in xs) ou sum(x for x in xs))
+#[derive(Debug, Clone)]
+pub struct GeneratorExpression {
+    pub token: Token,
+    pub element: Box<Expression>,
+    pub clauses: Vec<ComprehensionClause>,
+    pub scope: ComprehensionScope,
+    pub span: Span,
+}
+
+impl Node for GeneratorExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let clauses: Vec<String> = self.clauses.iter().map(|c| c.string()).collect();
+        format!("({} {})", self.element.string(), clauses.join(" "))
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // AttributeExpression: Acesso a atributo (objeto.atributo)
@@ -826,6 +1832,7 @@ pub struct AttributeExpression {
     pub token: Token,
     pub object: Box<Expression>,
     pub attribute: Identifier,
+    pub span: Span,
 }
 
 impl Node for AttributeExpression {
@@ -836,6 +1843,10 @@ impl Node for AttributeExpression {
     fn string(&self) -> String {
         format!("{}.{}", self.object.string(), self.attribute.string())
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
 // AssignmentExpression: Expressão de atribuição
@@ -844,7 +1855,8 @@ pub struct AssignmentExpression {
     pub token: Token,
     pub left: Box<Expression>,
     pub value: Box<Expression>,
-    pub operator: String, // "=", "+=", "-=", etc.
+    pub operator: String, // "=", "+=", "-=", ":=", etc.
+    pub span: Span,
 }
 
 impl Node for AssignmentExpression {
@@ -852,8 +1864,18 @@ impl Node for AssignmentExpression {
         self.token.literal.clone()
     }
 
+    // `:=` é exibido entre parênteses, a única forma em que pode aparecer num statement
     fn string(&self) -> String {
-        format!("{} {} {}", self.left.string(), self.operator, self.value.string())
+        let assignment = format!("{} {} {}", self.left.string(), self.operator, self.value.string());
+        if self.operator == ":=" {
+            format!("({})", assignment)
+        } else {
+            assignment
+        }
+    }
+
+    fn span(&self) -> Span {
+        self.span
     }
 }
 
@@ -861,8 +1883,9 @@ impl Node for AssignmentExpression {
 #[derive(Debug, Clone)]
 pub struct LambdaExpression {
     pub token: Token,
-    pub parameters: Vec<Identifier>,
+    pub parameters: Vec<Parameter>,
     pub body: Box<Expression>,
+    pub span: Span,
 }
 
 impl Node for LambdaExpression {
@@ -874,28 +1897,72 @@ impl Node for LambdaExpression {
         let mut out = String::new();
         out.push_str("lambda ");
         
-        let params: Vec<String> = self.parameters.iter().map(|p| p.string()).collect();
-        out.push_str(&params.join(", "));
+        out.push_str(&parameters_string(&self.parameters));
         
         out.push_str(": ");
         out.push_str(&self.body.string());
         
         out
     }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+}
+
+// YieldExpression: Produz um valor em um gerador (yield x ou yield from iterável)
+#[derive(Debug, Clone)]
+pub struct YieldExpression {
+    pub token: Token,
+    pub value: Option<Box<Expression>>,
+    pub is_from: bool,
+    pub span: Span,
+}
+
+impl Node for YieldExpression {
+    fn token_literal(&self) -> String {
+        self.token.literal.clone()
+    }
+
+    fn string(&self) -> String {
+        let mut out = String::new();
+        out.push_str("(yield");
+        
+        if self.is_from {
+            out.push_str(" from");
+        }
+        
+        if let Some(value) = &self.value {
+            out.push(' ');
+            out.push_str(&value.string());
+        }
+        
+        out.push(')');
+        out
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
 }
 
-// Precedência para operadores
+// Precedência para operadores, da menor para a maior, seguindo a do Python
 #[derive(PartialEq, PartialOrd, Debug, Clone, Copy)]
 pub enum Precedence {
     Lowest,
-    Assignment,  // =, +=, -=, etc.
-    Logical,     // and, or
-    Equals,      // ==, !=
-    LessGreater, // >, <, >=, <=
+    Assignment,  // =, +=, -=, :=, etc.
+    Or,          // or
+    And,         // and
+    Not,         // not X
+    Comparison,  // ==, !=, <, >, <=, >=, in, not in, is, is not
+    BitwiseOr,   // |
+    BitwiseXor,  // ^
+    BitwiseAnd,  // &
+    Shift,       // <<, >>
     Sum,         // +, -
-    Product,     // *, /, %
-    Power,       // **
-    Prefix,      // -X, !X
+    Product,     // *, /, //, %, @
+    Prefix,      // -X, +X, !X, ~X
+    Power,       // ** (associa à direita e prende mais que o prefixo à sua esquerda: -2 ** 2 == -(2 ** 2))
     Call,        // myFunction(X)
     Index,       // array[index]
     Attribute,   // obj.attribute
@@ -906,16 +1973,21 @@ impl fmt::Display for Precedence {
         match self {
             Precedence::Lowest => write!(f, "LOWEST"),
             Precedence::Assignment => write!(f, "ASSIGNMENT"),
-            Precedence::Logical => write!(f, "LOGICAL"),
-            Precedence::Equals => write!(f, "EQUALS"),
-            Precedence::LessGreater => write!(f, "LESSGREATER"),
+            Precedence::Or => write!(f, "OR"),
+            Precedence::And => write!(f, "AND"),
+            Precedence::Not => write!(f, "NOT"),
+            Precedence::Comparison => write!(f, "COMPARISON"),
+            Precedence::BitwiseOr => write!(f, "BITWISEOR"),
+            Precedence::BitwiseXor => write!(f, "BITWISEXOR"),
+            Precedence::BitwiseAnd => write!(f, "BITWISEAND"),
+            Precedence::Shift => write!(f, "SHIFT"),
             Precedence::Sum => write!(f, "SUM"),
             Precedence::Product => write!(f, "PRODUCT"),
-            Precedence::Power => write!(f, "POWER"),
             Precedence::Prefix => write!(f, "PREFIX"),
+            Precedence::Power => write!(f, "POWER"),
             Precedence::Call => write!(f, "CALL"),
             Precedence::Index => write!(f, "INDEX"),
             Precedence::Attribute => write!(f, "ATTRIBUTE"),
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,794 @@
+// parser/dump.rs
+// Exibição da AST como árvore legível ou como JSON, usada pelo comando `odin ast`
+
+use crate::lexer::Span;
+use crate::parser::ast::*;
+
+
+
+/// Nó genérico com o tipo, o trecho do código-fonte e os campos de um nó da AST
+#[derive(Debug, Clone, PartialEq)]
+pub struct DumpNode {
+    pub kind: &'static str,
+    pub span: Span,
+    pub fields: Vec<(&'static str, DumpValue)>,
+}
+
+/// Valor de um campo de `DumpNode`
+#[derive(Debug, Clone, PartialEq)]
+pub enum DumpValue {
+    Text(String),
+    Number(String), // inteiros e floats, exibidos sem aspas
+    Bool(bool),
+    Null,
+    Node(DumpNode),
+    List(Vec<DumpValue>),
+}
+
+/// Converte um nó da AST para `DumpNode`
+pub trait Dump {
+    fn dump(&self) -> DumpNode;
+}
+
+impl DumpNode {
+    pub fn new(kind: &'static str, span: Span) -> Self {
+        DumpNode { kind, span, fields: Vec::new() }
+    }
+
+    /// Adiciona um campo ao nó
+    pub fn field(mut self, name: &'static str, value: DumpValue) -> Self {
+        self.fields.push((name, value));
+        self
+    }
+
+    /// Árvore indentada, com um nó ou campo por linha
+    pub fn to_tree(&self) -> String {
+        let mut out = String::new();
+        self.write_tree(&mut out, 0);
+        out
+    }
+
+    /// JSON com um objeto por nó, contendo `kind`, `span` e os campos do nó
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out, 0);
+        out
+    }
+
+    fn header(&self) -> String {
+        format!("{} [{}..{}]", self.kind, self.span.start, self.span.end)
+    }
+
+    fn write_tree(&self, out: &mut String, depth: usize) {
+        out.push_str(&self.header());
+        out.push('\n');
+
+        for (name, value) in &self.fields {
+            out.push_str(&"  ".repeat(depth + 1));
+            out.push_str(name);
+            out.push(':');
+            write_tree_value(value, out, depth + 1);
+        }
+    }
+
+    fn write_json(&self, out: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth + 1);
+
+        out.push_str("{\n");
+        out.push_str(&format!("{}\"kind\": {},\n", indent, json_string(self.kind)));
+        out.push_str(&format!(
+            "{}\"span\": {{\"file_id\": {}, \"start\": {}, \"end\": {}}}",
+            indent, self.span.file_id, self.span.start, self.span.end
+        ));
+
+        for (name, value) in &self.fields {
+            out.push_str(&format!(",\n{}{}: ", indent, json_string(name)));
+            write_json_value(value, out, depth + 1);
+        }
+
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+        out.push('}');
+    }
+}
+
+// Escreve o valor de um campo logo após o "nome:" já escrito na linha
+fn write_tree_value(value: &DumpValue, out: &mut String, depth: usize) {
+    match value {
+        DumpValue::Node(node) => {
+            out.push(' ');
+            node.write_tree(out, depth);
+        },
+        DumpValue::List(items) if items.is_empty() => out.push_str(" []\n"),
+        DumpValue::List(items) => {
+            out.push('\n');
+            for item in items {
+                out.push_str(&"  ".repeat(depth + 1));
+                out.push('-');
+                write_tree_value(item, out, depth + 1);
+            }
+        },
+        scalar => {
+            out.push(' ');
+            out.push_str(&scalar_text(scalar));
+            out.push('\n');
+        },
+    }
+}
+
+fn write_json_value(value: &DumpValue, out: &mut String, depth: usize) {
+    match value {
+        DumpValue::Node(node) => node.write_json(out, depth),
+        DumpValue::List(items) if items.is_empty() => out.push_str("[]"),
+        DumpValue::List(items) => {
+            out.push_str("[\n");
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push_str(",\n");
+                }
+                out.push_str(&"  ".repeat(depth + 1));
+                write_json_value(item, out, depth + 1);
+            }
+            out.push('\n');
+            out.push_str(&"  ".repeat(depth));
+            out.push(']');
+        },
+        scalar => out.push_str(&scalar_text(scalar)),
+    }
+}
+
+// Texto de um valor simples, igual na árvore e no JSON
+fn scalar_text(value: &DumpValue) -> String {
+    match value {
+        DumpValue::Text(text) => json_string(text),
+        DumpValue::Number(number) => number.clone(),
+        DumpValue::Bool(value) => value.to_string(),
+        DumpValue::Null => String::from("null"),
+        DumpValue::Node(_) | DumpValue::List(_) => unreachable!(),
+    }
+}
+
+/// String JSON entre aspas, com os caracteres especiais escapados
+pub fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Funções auxiliares para montar os campos
+
+fn text(value: &str) -> DumpValue {
+    DumpValue::Text(value.to_string())
+}
+
+fn node(value: &impl Dump) -> DumpValue {
+    DumpValue::Node(value.dump())
+}
+
+fn optional<T: Dump>(value: Option<&T>) -> DumpValue {
+    value.map_or(DumpValue::Null, node)
+}
+
+fn list<'a, T: Dump + 'a>(values: impl IntoIterator<Item = &'a T>) -> DumpValue {
+    DumpValue::List(values.into_iter().map(node).collect())
+}
+
+// Floats infinitos ou NaN não são números válidos em JSON
+fn float(value: f64) -> DumpValue {
+    if value.is_finite() {
+        DumpValue::Number(format!("{:?}", value))
+    } else {
+        DumpValue::Text(value.to_string())
+    }
+}
+
+// ===== Statements =====
+
+impl Dump for Program {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("Program", self.span).field("statements", list(&self.statements))
+    }
+}
+
+impl Dump for Statement {
+    fn dump(&self) -> DumpNode {
+        match self {
+            Statement::Let(s) => s.dump(),
+            Statement::Return(s) => s.dump(),
+            Statement::Expression(s) => s.dump(),
+            Statement::Block(s) => s.dump(),
+            Statement::If(s) => s.dump(),
+            Statement::While(s) => s.dump(),
+            Statement::For(s) => s.dump(),
+            Statement::Function(s) => s.dump(),
+            Statement::Class(s) => s.dump(),
+            Statement::Import(s) => s.dump(),
+            Statement::With(s) => s.dump(),
+            Statement::Try(s) => s.dump(),
+            Statement::Pass(s) => s.dump(),
+            Statement::Break(s) => s.dump(),
+            Statement::Continue(s) => s.dump(),
+            Statement::Raise(s) => s.dump(),
+        }
+    }
+}
+
+impl Dump for LetStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("LetStatement", self.span)
+            .field("name", node(&self.name))
+            .field("value", optional(self.value.as_ref()))
+    }
+}
+
+impl Dump for ReturnStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("ReturnStatement", self.span).field("value", optional(self.return_value.as_ref()))
+    }
+}
+
+impl Dump for ExpressionStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("ExpressionStatement", self.span).field("expression", optional(self.expression.as_ref()))
+    }
+}
+
+impl Dump for BlockStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("BlockStatement", self.span).field("statements", list(&self.statements))
+    }
+}
+
+impl Dump for IfStatement {
+    fn dump(&self) -> DumpNode {
+        let elif_branches = self
+            .elif_branches
+            .iter()
+            .map(|(condition, body)| {
+                DumpValue::Node(
+                    DumpNode::new("ElifBranch", condition.span().merge(body.span))
+                        .field("condition", node(condition))
+                        .field("body", node(body)),
+                )
+            })
+            .collect();
+
+        DumpNode::new("IfStatement", self.span)
+            .field("condition", node(&self.condition))
+            .field("consequence", node(&self.consequence))
+            .field("elif_branches", DumpValue::List(elif_branches))
+            .field("alternative", optional(self.alternative.as_ref()))
+    }
+}
+
+impl Dump for WhileStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("WhileStatement", self.span)
+            .field("condition", node(&self.condition))
+            .field("body", node(&self.body))
+    }
+}
+
+impl Dump for ForStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("ForStatement", self.span)
+            .field("iterator", node(self.iterator.as_ref()))
+            .field("iterable", node(self.iterable.as_ref()))
+            .field("body", node(&self.body))
+            .field("alternative", optional(self.alternative.as_ref()))
+    }
+}
+
+impl Dump for FunctionStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("FunctionStatement", self.span)
+            .field("name", node(&self.name))
+            .field("parameters", list(&self.parameters))
+            .field("return_type", optional(self.return_type.as_ref()))
+            .field("body", node(&self.body))
+    }
+}
+
+impl Dump for ClassStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("ClassStatement", self.span)
+            .field("name", node(&self.name))
+            .field("parent", optional(self.parent.as_ref()))
+            .field("methods", list(&self.methods))
+            .field("body", list(&self.body))
+    }
+}
+
+impl Dump for ImportStatement {
+    fn dump(&self) -> DumpNode {
+        let items = self
+            .items
+            .iter()
+            .map(|(name, alias)| {
+                DumpValue::Node(
+                    DumpNode::new("ImportItem", self.span)
+                        .field("name", text(name))
+                        .field("alias", alias.as_deref().map_or(DumpValue::Null, text)),
+                )
+            })
+            .collect();
+
+        DumpNode::new("ImportStatement", self.span)
+            .field("module", text(&self.module))
+            .field("items", DumpValue::List(items))
+            .field("is_from", DumpValue::Bool(self.is_from))
+    }
+}
+
+impl Dump for TryStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("TryStatement", self.span)
+            .field("try_block", node(&self.try_block))
+            .field("except_blocks", list(&self.except_blocks))
+            .field("finally_block", optional(self.finally_block.as_ref()))
+    }
+}
+
+impl Dump for ExceptClause {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("ExceptClause", self.span)
+            .field("exception", optional(self.exception.as_ref()))
+            .field("name", optional(self.name.as_ref()))
+            .field("body", node(&self.body))
+    }
+}
+
+impl Dump for WithStatement {
+    fn dump(&self) -> DumpNode {
+        let items = self
+            .items
+            .iter()
+            .map(|(context, target)| {
+                let span = target.as_ref().map_or(context.span(), |t| context.span().merge(t.span()));
+                DumpValue::Node(
+                    DumpNode::new("WithItem", span)
+                        .field("context", node(context))
+                        .field("target", optional(target.as_ref())),
+                )
+            })
+            .collect();
+
+        DumpNode::new("WithStatement", self.span)
+            .field("items", DumpValue::List(items))
+            .field("body", node(&self.body))
+    }
+}
+
+impl Dump for PassStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("PassStatement", self.span)
+    }
+}
+
+impl Dump for BreakStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("BreakStatement", self.span)
+    }
+}
+
+impl Dump for ContinueStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("ContinueStatement", self.span)
+    }
+}
+
+impl Dump for RaiseStatement {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("RaiseStatement", self.span)
+            .field("exception", optional(self.exception.as_ref()))
+            .field("cause", optional(self.cause.as_ref()))
+    }
+}
+
+// ===== Expressions =====
+
+impl Dump for Expression {
+    fn dump(&self) -> DumpNode {
+        match self {
+            Expression::Identifier(e) => e.dump(),
+            Expression::IntegerLiteral(e) => e.dump(),
+            Expression::FloatLiteral(e) => e.dump(),
+            Expression::ImaginaryLiteral(e) => e.dump(),
+            Expression::StringLiteral(e) => e.dump(),
+            Expression::BytesLiteral(e) => e.dump(),
+            Expression::FString(e) => e.dump(),
+            Expression::Boolean(e) => e.dump(),
+            Expression::None(e) => e.dump(),
+            Expression::Prefix(e) => e.dump(),
+            Expression::Infix(e) => e.dump(),
+            Expression::Logical(e) => e.dump(),
+            Expression::Comparison(e) => e.dump(),
+            Expression::If(e) => e.dump(),
+            Expression::FunctionLiteral(e) => e.dump(),
+            Expression::Call(e) => e.dump(),
+            Expression::Index(e) => e.dump(),
+            Expression::Slice(e) => e.dump(),
+            Expression::Array(e) => e.dump(),
+            Expression::Tuple(e) => e.dump(),
+            Expression::Starred(e) => e.dump(),
+            Expression::Dict(e) => e.dump(),
+            Expression::ListComprehension(e) => e.dump(),
+            Expression::SetComprehension(e) => e.dump(),
+            Expression::DictComprehension(e) => e.dump(),
+            Expression::Generator(e) => e.dump(),
+            Expression::Attribute(e) => e.dump(),
+            Expression::Assignment(e) => e.dump(),
+            Expression::Lambda(e) => e.dump(),
+            Expression::Yield(e) => e.dump(),
+        }
+    }
+}
+
+impl Dump for Identifier {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("Identifier", self.span).field("value", text(&self.value))
+    }
+}
+
+impl Dump for IntegerLiteral {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("IntegerLiteral", self.span).field("value", DumpValue::Number(self.value.to_string()))
+    }
+}
+
+impl Dump for FloatLiteral {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("FloatLiteral", self.span).field("value", float(self.value))
+    }
+}
+
+impl Dump for ImaginaryLiteral {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("ImaginaryLiteral", self.span).field("value", float(self.value))
+    }
+}
+
+impl Dump for StringLiteral {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("StringLiteral", self.span).field("value", text(&self.value))
+    }
+}
+
+impl Dump for BytesLiteral {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("BytesLiteral", self.span).field("value", text(&self.value.escape_ascii().to_string()))
+    }
+}
+
+impl Dump for FStringLiteral {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("FStringLiteral", self.span).field("parts", fstring_parts(&self.parts, self.span))
+    }
+}
+
+// Trechos de uma f-string ou de uma especificação de formato com campos aninhados
+fn fstring_parts(parts: &[FStringPart], span: Span) -> DumpValue {
+    let parts = parts
+        .iter()
+        .map(|part| match part {
+            FStringPart::Literal(value) => {
+                DumpValue::Node(DumpNode::new("FStringText", span).field("value", text(value)))
+            },
+            FStringPart::Field { expression, conversion, format_spec } => DumpValue::Node(
+                DumpNode::new("FStringField", expression.span())
+                    .field("expression", node(expression.as_ref()))
+                    .field("conversion", conversion.map_or(DumpValue::Null, |c| DumpValue::Text(c.to_string())))
+                    .field("format_spec", fstring_parts(format_spec, span)),
+            ),
+        })
+        .collect();
+
+    DumpValue::List(parts)
+}
+
+impl Dump for Boolean {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("Boolean", self.span).field("value", DumpValue::Bool(self.value))
+    }
+}
+
+impl Dump for NoneLiteral {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("NoneLiteral", self.span)
+    }
+}
+
+impl Dump for PrefixExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("PrefixExpression", self.span)
+            .field("operator", text(&self.operator))
+            .field("right", node(self.right.as_ref()))
+    }
+}
+
+impl Dump for InfixExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("InfixExpression", self.span)
+            .field("operator", text(&self.operator))
+            .field("left", node(self.left.as_ref()))
+            .field("right", node(self.right.as_ref()))
+    }
+}
+
+impl Dump for LogicalExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("LogicalExpression", self.span)
+            .field("operator", text(&self.operator))
+            .field("left", node(self.left.as_ref()))
+            .field("right", node(self.right.as_ref()))
+    }
+}
+
+impl Dump for ComparisonExpression {
+    fn dump(&self) -> DumpNode {
+        let comparisons = self
+            .comparisons
+            .iter()
+            .map(|(operator, right)| {
+                DumpValue::Node(
+                    DumpNode::new("Comparison", right.span())
+                        .field("operator", text(operator))
+                        .field("right", node(right)),
+                )
+            })
+            .collect();
+
+        DumpNode::new("ComparisonExpression", self.span)
+            .field("left", node(self.left.as_ref()))
+            .field("comparisons", DumpValue::List(comparisons))
+    }
+}
+
+impl Dump for IfExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("IfExpression", self.span)
+            .field("condition", node(self.condition.as_ref()))
+            .field("consequence", node(&self.consequence))
+            .field("alternative", optional(self.alternative.as_ref()))
+    }
+}
+
+impl Dump for FunctionLiteral {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("FunctionLiteral", self.span)
+            .field("parameters", list(&self.parameters))
+            .field("return_type", optional(self.return_type.as_deref()))
+            .field("body", node(&self.body))
+    }
+}
+
+impl Dump for Parameter {
+    fn dump(&self) -> DumpNode {
+        let kind = match self.kind {
+            ParameterKind::Positional => "positional",
+            ParameterKind::VarPositional => "var_positional",
+            ParameterKind::KeywordOnly => "keyword_only",
+            ParameterKind::VarKeyword => "var_keyword",
+        };
+
+        DumpNode::new("Parameter", self.span)
+            .field("name", node(&self.name))
+            .field("kind", text(kind))
+            .field("default", optional(self.default.as_ref()))
+    }
+}
+
+impl Dump for Argument {
+    fn dump(&self) -> DumpNode {
+        let (kind, name) = match &self.kind {
+            ArgumentKind::Positional => ("positional", None),
+            ArgumentKind::Keyword(name) => ("keyword", Some(name)),
+            ArgumentKind::Unpack => ("unpack", None),
+            ArgumentKind::UnpackKeywords => ("unpack_keywords", None),
+        };
+
+        DumpNode::new("Argument", self.span)
+            .field("kind", text(kind))
+            .field("name", optional(name))
+            .field("value", node(&self.value))
+    }
+}
+
+impl Dump for CallExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("CallExpression", self.span)
+            .field("function", node(self.function.as_ref()))
+            .field("arguments", list(&self.arguments))
+    }
+}
+
+impl Dump for IndexExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("IndexExpression", self.span)
+            .field("left", node(self.left.as_ref()))
+            .field("index", node(self.index.as_ref()))
+    }
+}
+
+impl Dump for SliceExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("SliceExpression", self.span)
+            .field("start", optional(self.start.as_deref()))
+            .field("stop", optional(self.stop.as_deref()))
+            .field("step", optional(self.step.as_deref()))
+    }
+}
+
+impl Dump for ArrayLiteral {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("ArrayLiteral", self.span).field("elements", list(&self.elements))
+    }
+}
+
+impl Dump for TupleLiteral {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("TupleLiteral", self.span).field("elements", list(&self.elements))
+    }
+}
+
+impl Dump for StarredExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("StarredExpression", self.span).field("value", node(self.value.as_ref()))
+    }
+}
+
+impl Dump for DictLiteral {
+    fn dump(&self) -> DumpNode {
+        let pairs = self
+            .pairs
+            .iter()
+            .map(|(key, value)| {
+                DumpValue::Node(
+                    DumpNode::new("DictEntry", key.span().merge(value.span()))
+                        .field("key", node(key))
+                        .field("value", node(value)),
+                )
+            })
+            .collect();
+
+        DumpNode::new("DictLiteral", self.span).field("pairs", DumpValue::List(pairs))
+    }
+}
+
+impl Dump for ComprehensionClause {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("ComprehensionClause", self.span)
+            .field("target", node(&self.target))
+            .field("iterable", node(&self.iterable))
+            .field("conditions", list(&self.conditions))
+    }
+}
+
+impl Dump for ListComprehension {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("ListComprehension", self.span)
+            .field("element", node(self.element.as_ref()))
+            .field("clauses", list(&self.clauses))
+            .field("locals", list(&self.scope.locals))
+    }
+}
+
+impl Dump for SetComprehension {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("SetComprehension", self.span)
+            .field("element", node(self.element.as_ref()))
+            .field("clauses", list(&self.clauses))
+            .field("locals", list(&self.scope.locals))
+    }
+}
+
+impl Dump for DictComprehension {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("DictComprehension", self.span)
+            .field("key", node(self.key.as_ref()))
+            .field("value", node(self.value.as_ref()))
+            .field("clauses", list(&self.clauses))
+            .field("locals", list(&self.scope.locals))
+    }
+}
+
+impl Dump for GeneratorExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("GeneratorExpression", self.span)
+            .field("element", node(self.element.as_ref()))
+            .field("clauses", list(&self.clauses))
+            .field("locals", list(&self.scope.locals))
+    }
+}
+
+impl Dump for AttributeExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("AttributeExpression", self.span)
+            .field("object", node(self.object.as_ref()))
+            .field("attribute", node(&self.attribute))
+    }
+}
+
+impl Dump for AssignmentExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("AssignmentExpression", self.span)
+            .field("operator", text(&self.operator))
+            .field("target", node(self.left.as_ref()))
+            .field("value", node(self.value.as_ref()))
+    }
+}
+
+impl Dump for LambdaExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("LambdaExpression", self.span)
+            .field("parameters", list(&self.parameters))
+            .field("body", node(self.body.as_ref()))
+    }
+}
+
+impl Dump for YieldExpression {
+    fn dump(&self) -> DumpNode {
+        DumpNode::new("YieldExpression", self.span)
+            .field("value", optional(self.value.as_deref()))
+            .field("is_from", DumpValue::Bool(self.is_from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+    use crate::parser::parser::Parser;
+
+    fn dump_source(input: &str) -> DumpNode {
+        let mut parser = Parser::new(tokenize(input).unwrap());
+        let program = parser.parse_program();
+        assert!(parser.errors().is_empty(), "{:?}", parser.errors());
+        program.dump()
+    }
+
+    #[test]
+    fn test_tree_dump() {
+        let tree = dump_source("x = 1 + 2\n").to_tree();
+        let expected = "\
+Program [0..10]
+  statements:
+    - ExpressionStatement [0..9]
+      expression: AssignmentExpression [0..9]
+        operator: \"=\"
+        target: Identifier [0..1]
+          value: \"x\"
+        value: InfixExpression [4..9]
+          operator: \"+\"
+          left: IntegerLiteral [4..5]
+            value: 1
+          right: IntegerLiteral [8..9]
+            value: 2
+";
+        assert_eq!(tree, expected);
+    }
+
+    #[test]
+    fn test_json_dump() {
+        let json = dump_source("f('a\\n', None)\n").to_json();
+
+        assert!(json.starts_with("{\n  \"kind\": \"Program\",\n  \"span\": {\"file_id\": 0, \"start\": 0, \"end\": 15},"));
+        assert!(json.contains("\"kind\": \"CallExpression\""));
+        assert!(json.contains("\"value\": \"a\\n\""));
+        assert!(json.contains("\"kind\": \"NoneLiteral\""));
+        assert_eq!(json.matches('{').count(), json.matches('}').count());
+        assert_eq!(json.matches('[').count(), json.matches(']').count());
+    }
+}
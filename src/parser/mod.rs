@@ -3,17 +3,18 @@
 
 pub mod ast;
 pub mod parser;
+pub mod dump;
 
 
 
 // Re-exportações para facilitar o uso
 pub use self::ast::{
-    Program, Statement, Expression, 
+    Program, Statement, Expression,
     LetStatement, ReturnStatement, ExpressionStatement, BlockStatement,
-    Identifier, IntegerLiteral, BooleanLiteral, StringLiteral,
+    ForStatement, FunctionStatement, ClassStatement,
+    Identifier, IntegerLiteral, Boolean, StringLiteral,
     PrefixExpression, InfixExpression, IfExpression, FunctionLiteral,
-    CallExpression, ArrayLiteral, IndexExpression, HashLiteral,
-    ForStatement, ClassStatement, MethodStatement, PropertyAccessExpression,
+    CallExpression, ArrayLiteral, IndexExpression, DictLiteral, AttributeExpression,
 };
 pub use self::parser::Parser;
 
@@ -24,15 +25,28 @@ pub fn new_parser(tokens: Vec<Token>) -> Parser {
     Parser::new(tokens)
 }
 
+/// Analisa os tokens produzidos por `lexer::tokenize`. Os erros do parser, se houver,
+/// são devolvidos juntos, um por linha
+pub fn parse(tokens: Vec<Token>) -> Result<Program, String> {
+    let mut parser = Parser::new(tokens);
+    let program = parser.parse_program();
+    
+    if parser.errors().is_empty() {
+        Ok(program)
+    } else {
+        Err(parser.errors().join("\n"))
+    }
+}
+
 // Função auxiliar para criar um parser diretamente de uma string de entrada
 // usando o lexer para gerar os tokens
 pub fn parse_from_source(input: &str) -> (Program, Vec<String>) {
-    use crate::lexer::lexer::Lexer;
-    
-    let lexer = Lexer::new(input.to_string());
-    let tokens = lexer.tokenize();
+    let tokens = match crate::lexer::tokenize(input) {
+        Ok(tokens) => tokens,
+        Err(error) => return (Program::new(), vec![error]),
+    };
     let mut parser = Parser::new(tokens);
     let program = parser.parse_program();
     
     (program, parser.errors().clone())
-}
\ No newline at end of file
+}
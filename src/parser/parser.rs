@@ -1,10 +1,15 @@
-use crate::lexer::token::{Token, TokenType};
+use crate::lexer::token::{Span, Token, TokenType, int_literal_value, float_literal_value};
 use crate::parser::ast::{
-    BlockStatement, Expression, ExpressionStatement, Identifier, IfExpression, InfixExpression,
-    LetStatement, PrefixExpression, Program, ReturnStatement, Statement, StringLiteral,
-    IntegerLiteral, BooleanLiteral, FunctionLiteral, CallExpression, ArrayLiteral,
-    IndexExpression, HashLiteral, ForStatement, ClassStatement, MethodStatement, 
-    PropertyAccessExpression,
+    Argument, ArgumentKind, ArrayLiteral, AssignmentExpression, AttributeExpression, BlockStatement,
+    Boolean, BreakStatement, BytesLiteral, CallExpression, ClassStatement, ComparisonExpression,
+    ComprehensionClause, ComprehensionScope, ContinueStatement, DictComprehension, DictLiteral, ExceptClause,
+    Expression, ExpressionStatement, FloatLiteral, ForStatement, FStringLiteral, FStringPart,
+    FunctionLiteral, FunctionStatement, GeneratorExpression, Identifier, IfStatement,
+    ImaginaryLiteral, ImportStatement, IndexExpression, InfixExpression, IntegerLiteral,
+    LambdaExpression, LetStatement, ListComprehension, LogicalExpression, Node, NoneLiteral,
+    Parameter, ParameterKind, PassStatement, Precedence, PrefixExpression, Program, RaiseStatement,
+    ReturnStatement, SetComprehension, SliceExpression, StarredExpression, Statement, StringLiteral,
+    TryStatement, TupleLiteral, WhileStatement, WithStatement, YieldExpression,
 };
 use std::collections::HashMap;
 
@@ -13,25 +18,13 @@ use std::collections::HashMap;
 type PrefixParseFn = fn(&mut Parser) -> Option<Expression>;
 type InfixParseFn = fn(&mut Parser, Expression) -> Option<Expression>;
 
-#[derive(Debug, PartialEq, PartialOrd, Clone)]
-enum Precedence {
-    Lowest,
-    Equals,      // ==
-    LessGreater, // > or <
-    Sum,         // +
-    Product,     // *
-    Prefix,      // -X or !X
-    Call,        // myFunction(X)
-    Index,       // array[index]
-    Member,      // obj.property
-}
-
 pub struct Parser {
     tokens: Vec<Token>,
     position: usize,
     current_token: Token,
     peek_token: Token,
     errors: Vec<String>,
+    loop_depth: usize, // loops abertos na função atual, para validar break e continue
     prefix_parse_fns: HashMap<TokenType, PrefixParseFn>,
     infix_parse_fns: HashMap<TokenType, InfixParseFn>,
 }
@@ -41,39 +34,72 @@ impl Parser {
         let mut parser = Parser {
             tokens,
             position: 0,
-            current_token: Token::new(TokenType::Illegal, "".to_string()),
-            peek_token: Token::new(TokenType::Illegal, "".to_string()),
+            current_token: Token::new(TokenType::Illegal, String::new(), 0, 0),
+            peek_token: Token::new(TokenType::Illegal, String::new(), 0, 0),
             errors: Vec::new(),
+            loop_depth: 0,
             prefix_parse_fns: HashMap::new(),
             infix_parse_fns: HashMap::new(),
         };
 
         // Register prefix parse functions
         parser.register_prefix(TokenType::Identifier, Parser::parse_identifier);
-        parser.register_prefix(TokenType::Integer, Parser::parse_integer_literal);
+        parser.register_prefix(TokenType::Int, Parser::parse_integer_literal);
+        parser.register_prefix(TokenType::Float, Parser::parse_float_literal);
+        parser.register_prefix(TokenType::Imaginary, Parser::parse_imaginary_literal);
         parser.register_prefix(TokenType::String, Parser::parse_string_literal);
+        parser.register_prefix(TokenType::Bytes, Parser::parse_bytes_literal);
+        parser.register_prefix(TokenType::FStringStart, Parser::parse_fstring_literal);
         parser.register_prefix(TokenType::True, Parser::parse_boolean_literal);
         parser.register_prefix(TokenType::False, Parser::parse_boolean_literal);
+        parser.register_prefix(TokenType::None, Parser::parse_none_literal);
         parser.register_prefix(TokenType::Bang, Parser::parse_prefix_expression);
+        parser.register_prefix(TokenType::Not, Parser::parse_prefix_expression);
+        parser.register_prefix(TokenType::Plus, Parser::parse_prefix_expression);
         parser.register_prefix(TokenType::Minus, Parser::parse_prefix_expression);
+        parser.register_prefix(TokenType::Tilde, Parser::parse_prefix_expression);
         parser.register_prefix(TokenType::LParen, Parser::parse_grouped_expression);
-        parser.register_prefix(TokenType::If, Parser::parse_if_expression);
         parser.register_prefix(TokenType::Function, Parser::parse_function_literal);
+        parser.register_prefix(TokenType::Lambda, Parser::parse_lambda_expression);
+        parser.register_prefix(TokenType::Yield, Parser::parse_yield_expression);
         parser.register_prefix(TokenType::LBracket, Parser::parse_array_literal);
-        parser.register_prefix(TokenType::LBrace, Parser::parse_hash_literal);
+        parser.register_prefix(TokenType::LBrace, Parser::parse_dict_literal);
 
         // Register infix parse functions
         parser.register_infix(TokenType::Plus, Parser::parse_infix_expression);
         parser.register_infix(TokenType::Minus, Parser::parse_infix_expression);
         parser.register_infix(TokenType::Slash, Parser::parse_infix_expression);
         parser.register_infix(TokenType::Asterisk, Parser::parse_infix_expression);
-        parser.register_infix(TokenType::Eq, Parser::parse_infix_expression);
-        parser.register_infix(TokenType::NotEq, Parser::parse_infix_expression);
-        parser.register_infix(TokenType::Lt, Parser::parse_infix_expression);
-        parser.register_infix(TokenType::Gt, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::FloorDiv, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::Percent, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::At, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::Power, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::Ampersand, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::Pipe, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::Caret, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::ShiftLeft, Parser::parse_infix_expression);
+        parser.register_infix(TokenType::ShiftRight, Parser::parse_infix_expression);
+        for token_type in [
+            TokenType::Eq,
+            TokenType::NotEq,
+            TokenType::Lt,
+            TokenType::Gt,
+            TokenType::LtEq,
+            TokenType::GtEq,
+            TokenType::In,
+            TokenType::Not, // not in
+            TokenType::Is,  // is e is not
+        ] {
+            parser.register_infix(token_type, Parser::parse_comparison_expression);
+        }
+        parser.register_infix(TokenType::And, Parser::parse_logical_expression);
+        parser.register_infix(TokenType::Or, Parser::parse_logical_expression);
         parser.register_infix(TokenType::LParen, Parser::parse_call_expression);
         parser.register_infix(TokenType::LBracket, Parser::parse_index_expression);
-        parser.register_infix(TokenType::Dot, Parser::parse_property_access_expression);
+        parser.register_infix(TokenType::Dot, Parser::parse_attribute_expression);
+        // `=` e os operadores aumentados só aparecem em statements (parse_assignment_statement);
+        // dentro de expressões, apenas `:=` atribui
+        parser.register_infix(TokenType::Walrus, Parser::parse_walrus_expression);
 
         // Read two tokens to initialize current_token and peek_token
         parser.next_token();
@@ -88,12 +114,16 @@ impl Parser {
 
     fn next_token(&mut self) {
         self.current_token = self.peek_token.clone();
-        
+
         if self.position < self.tokens.len() {
             self.peek_token = self.tokens[self.position].clone();
             self.position += 1;
         } else {
-            self.peek_token = Token::new(TokenType::EOF, "".to_string());
+            // Depois do fim, o EOF do lexer se repete, mantendo o seu span
+            self.peek_token = match self.tokens.last() {
+                Some(token) if token.token_type == TokenType::Eof => token.clone(),
+                _ => Token::new(TokenType::Eof, String::new(), 0, 0),
+            };
         }
     }
 
@@ -117,8 +147,8 @@ impl Parser {
 
     fn peek_error(&mut self, token_type: TokenType) {
         let msg = format!(
-            "expected next token to be {:?}, got {:?} instead",
-            token_type, self.peek_token.token_type
+            "expected next token to be {:?}, got {:?} instead at line {} column {}",
+            token_type, self.peek_token.token_type, self.peek_token.line, self.peek_token.column
         );
         self.errors.push(msg);
     }
@@ -131,16 +161,62 @@ impl Parser {
         self.infix_parse_fns.insert(token_type, function);
     }
 
+    // Span de um nó que começa em `start` e termina no token atual
+    fn span_from(&self, start: Span) -> Span {
+        start.merge(self.current_token.span)
+    }
+
+    // O bloco vai do ':' até o fim do último statement, sem o NEWLINE ou DEDENT que o encerra
+    fn block_span(token: &Token, statements: &[Statement]) -> Span {
+        match statements.last() {
+            Some(stmt) => token.span.merge(stmt.span()),
+            None => token.span,
+        }
+    }
+
+    // Identificador a partir do token atual
+    fn current_identifier(&self) -> Identifier {
+        Identifier {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+            span: self.current_token.span,
+        }
+    }
+
+    // Um statement termina no fim da linha, do arquivo ou do bloco
+    fn peek_ends_statement(&self) -> bool {
+        matches!(
+            self.peek_token.token_type,
+            TokenType::Newline | TokenType::Semicolon | TokenType::Dedent | TokenType::Eof
+        )
+    }
+
+    // Descarta o restante do statement depois de um erro, para que os tokens que
+    // sobram não gerem outros erros nem sejam lidos como novos statements
+    fn skip_statement(&mut self) {
+        while !self.peek_ends_statement() {
+            self.next_token();
+        }
+    }
+
     pub fn parse_program(&mut self) -> Program {
-        let mut program = Program { statements: Vec::new() };
+        let mut program = Program::new();
+        let start = self.current_token.span;
+
+        while !self.current_token_is(TokenType::Eof) {
+            // Linhas que terminam um statement não produzem nós
+            if self.current_token_is(TokenType::Newline) {
+                self.next_token();
+                continue;
+            }
 
-        while !self.current_token_is(TokenType::EOF) {
             if let Some(stmt) = self.parse_statement() {
                 program.statements.push(stmt);
             }
             self.next_token();
         }
 
+        program.span = self.span_from(start);
         program
     }
 
@@ -148,8 +224,20 @@ impl Parser {
         match self.current_token.token_type {
             TokenType::Let => self.parse_let_statement(),
             TokenType::Return => self.parse_return_statement(),
+            TokenType::If => self.parse_if_statement(),
+            TokenType::While => self.parse_while_statement(),
             TokenType::For => self.parse_for_statement(),
+            // `func nome(...)` declara uma função; `func(...)` é uma função anônima
+            TokenType::Function if self.peek_token_is(TokenType::Identifier) => {
+                self.parse_function_statement().map(Statement::Function)
+            }
             TokenType::Class => self.parse_class_statement(),
+            TokenType::Try => self.parse_try_statement(),
+            TokenType::With => self.parse_with_statement(),
+            TokenType::Import | TokenType::From => self.parse_import_statement(),
+            TokenType::Raise => self.parse_raise_statement(),
+            TokenType::Break | TokenType::Continue => self.parse_loop_control_statement(),
+            TokenType::Pass => self.parse_pass_statement(),
             _ => self.parse_expression_statement(),
         }
     }
@@ -161,10 +249,7 @@ impl Parser {
             return None;
         }
 
-        let name = Identifier {
-            token: self.current_token.clone(),
-            value: self.current_token.literal.clone(),
-        };
+        let name = self.current_identifier();
 
         if !self.expect_peek(TokenType::Assign) {
             return None;
@@ -172,94 +257,260 @@ impl Parser {
 
         self.next_token();
 
-        let value = match self.parse_expression(Precedence::Lowest) {
-            Some(expr) => expr,
-            None => return None,
-        };
+        let value = self.parse_expression(Precedence::Lowest)?;
 
         if self.peek_token_is(TokenType::Semicolon) {
             self.next_token();
         }
 
         Some(Statement::Let(LetStatement {
+            span: self.span_from(let_token.span),
             token: let_token,
             name,
-            value,
+            value: Some(value),
         }))
     }
 
     fn parse_return_statement(&mut self) -> Option<Statement> {
         let return_token = self.current_token.clone();
 
-        self.next_token();
-
-        let return_value = match self.parse_expression(Precedence::Lowest) {
-            Some(expr) => expr,
-            None => return None,
+        // `return` sozinho devolve None; `return a, b` devolve uma tupla
+        let return_value = if self.peek_ends_statement() {
+            None
+        } else {
+            self.next_token();
+            Some(self.parse_tuple_expression(Precedence::Lowest)?)
         };
 
+        let span = self.span_from(return_token.span);
+
         if self.peek_token_is(TokenType::Semicolon) {
             self.next_token();
         }
 
         Some(Statement::Return(ReturnStatement {
+            span,
             token: return_token,
             return_value,
         }))
     }
 
-    fn parse_for_statement(&mut self) -> Option<Statement> {
-        let for_token = self.current_token.clone();
+    fn parse_if_statement(&mut self) -> Option<Statement> {
+        let if_token = self.current_token.clone();
 
-        if !self.expect_peek(TokenType::LParen) {
+        self.next_token();
+        let condition = self.parse_expression(Precedence::Lowest)?;
+
+        if !self.expect_peek(TokenType::Colon) {
             return None;
         }
 
-        self.next_token();
-        let initialization = match self.parse_statement() {
-            Some(stmt) => stmt,
-            None => return None,
+        let consequence = self.parse_block_statement();
+        let mut end = consequence.span;
+
+        let mut elif_branches = Vec::new();
+        while self.peek_token_is(TokenType::Elif) {
+            self.next_token();
+            self.next_token();
+            let condition = self.parse_expression(Precedence::Lowest)?;
+
+            if !self.expect_peek(TokenType::Colon) {
+                return None;
+            }
+
+            let block = self.parse_block_statement();
+            end = block.span;
+            elif_branches.push((condition, block));
+        }
+
+        let alternative = match self.parse_else_block()? {
+            Some(block) => {
+                end = block.span;
+                Some(block)
+            }
+            None => None,
         };
 
-        if !self.expect_peek(TokenType::Semicolon) {
+        Some(Statement::If(IfStatement {
+            span: if_token.span.merge(end),
+            token: if_token,
+            condition,
+            consequence,
+            alternative,
+            elif_branches,
+        }))
+    }
+
+    // Bloco `else:` opcional depois de outro bloco. Devolve None em caso de erro
+    fn parse_else_block(&mut self) -> Option<Option<BlockStatement>> {
+        if !self.peek_token_is(TokenType::Else) {
+            return Some(None);
+        }
+
+        self.next_token();
+
+        if !self.expect_peek(TokenType::Colon) {
             return None;
         }
 
+        Some(Some(self.parse_block_statement()))
+    }
+
+    fn parse_while_statement(&mut self) -> Option<Statement> {
+        let while_token = self.current_token.clone();
+
         self.next_token();
-        let condition = match self.parse_expression(Precedence::Lowest) {
-            Some(expr) => expr,
-            None => return None,
-        };
+        let condition = self.parse_expression(Precedence::Lowest)?;
 
-        if !self.expect_peek(TokenType::Semicolon) {
+        if !self.expect_peek(TokenType::Colon) {
             return None;
         }
 
+        let body = self.parse_loop_body();
+
+        Some(Statement::While(WhileStatement {
+            span: while_token.span.merge(body.span),
+            token: while_token,
+            condition,
+            body,
+        }))
+    }
+
+    fn parse_for_statement(&mut self) -> Option<Statement> {
+        let for_token = self.current_token.clone();
+
         self.next_token();
-        let update = match self.parse_statement() {
-            Some(stmt) => stmt,
-            None => return None,
-        };
+        let iterator = self.parse_target_list(TokenType::In)?;
 
-        if !self.expect_peek(TokenType::RParen) {
+        if !self.expect_peek(TokenType::In) {
             return None;
         }
 
-        if !self.expect_peek(TokenType::LBrace) {
+        // Como em Python, `for x in 1, 2:` itera sobre a tupla `(1, 2)`
+        self.next_token();
+        let iterable = self.parse_tuple_expression(Precedence::Lowest)?;
+
+        if !self.expect_peek(TokenType::Colon) {
             return None;
         }
 
-        let body = self.parse_block_statement();
+        let body = self.parse_loop_body();
+        let alternative = self.parse_else_block()?;
+
+        let end = match &alternative {
+            Some(block) => block.span,
+            None => body.span,
+        };
 
         Some(Statement::For(ForStatement {
+            span: for_token.span.merge(end),
             token: for_token,
-            initialization: Box::new(initialization),
-            condition,
-            update: Box::new(update),
+            iterator: Box::new(iterator),
+            iterable: Box::new(iterable),
             body,
+            alternative,
         }))
     }
 
+    // Corpo de um while ou for, onde break e continue são permitidos
+    fn parse_loop_body(&mut self) -> BlockStatement {
+        self.loop_depth += 1;
+        let body = self.parse_block_statement();
+        self.loop_depth -= 1;
+        body
+    }
+
+    // Corpo de uma função: break e continue não se referem aos loops de fora dela
+    fn parse_function_body(&mut self) -> BlockStatement {
+        let loop_depth = std::mem::take(&mut self.loop_depth);
+        let body = self.parse_block_statement();
+        self.loop_depth = loop_depth;
+        body
+    }
+
+    // Alvos separados por vírgula a partir do token atual, até `end` (que não é
+    // consumido). Mais de um alvo, ou uma vírgula final, formam uma tupla
+    fn parse_target_list(&mut self, end: TokenType) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let first = self.parse_target()?;
+
+        if !self.peek_token_is(TokenType::Comma) {
+            if matches!(first, Expression::Starred(_)) {
+                self.errors.push(format!(
+                    "starred assignment target must be in a list or tuple at line {} column {}",
+                    token.line, token.column
+                ));
+                return None;
+            }
+            return Some(first);
+        }
+
+        let mut elements = vec![first];
+        while self.peek_token_is(TokenType::Comma) {
+            self.next_token();
+            if self.peek_token_is(end) {
+                break;
+            }
+            self.next_token();
+            elements.push(self.parse_target()?);
+        }
+
+        if elements.iter().filter(|e| matches!(e, Expression::Starred(_))).count() > 1 {
+            self.errors.push(format!(
+                "multiple starred expressions in assignment at line {} column {}",
+                token.line, token.column
+            ));
+            return None;
+        }
+
+        Some(Expression::Tuple(TupleLiteral { span: self.span_from(token.span), token, elements }))
+    }
+
+    // Um alvo de atribuição: nome, atributo, índice, alvos entre parênteses ou um
+    // alvo desempacotado (*resto)
+    fn parse_target(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+
+        if self.current_token_is(TokenType::LParen) {
+            self.next_token();
+            let target = self.parse_target_list(TokenType::RParen)?;
+
+            if !self.expect_peek(TokenType::RParen) {
+                return None;
+            }
+
+            // Os parênteses fazem parte do span da tupla
+            return Some(match target {
+                Expression::Tuple(tuple) => Expression::Tuple(TupleLiteral {
+                    span: self.span_from(token.span),
+                    token,
+                    elements: tuple.elements,
+                }),
+                target => target,
+            });
+        }
+
+        // Operandos de comparações, para que o `in` do for não seja consumido
+        let target = self.parse_tuple_element(Precedence::Comparison)?;
+
+        let simple = match &target {
+            Expression::Starred(starred) => starred.value.as_ref(),
+            target => target,
+        };
+
+        match simple {
+            Expression::Identifier(_) | Expression::Attribute(_) | Expression::Index(_) => Some(target),
+            _ => {
+                let msg = format!(
+                    "cannot assign to {} at line {} column {}",
+                    target.string(), token.line, token.column
+                );
+                self.errors.push(msg);
+                None
+            }
+        }
+    }
+
     fn parse_class_statement(&mut self) -> Option<Statement> {
         let class_token = self.current_token.clone();
 
@@ -267,577 +518,2652 @@ impl Parser {
             return None;
         }
 
-        let name = Identifier {
-            token: self.current_token.clone(),
-            value: self.current_token.literal.clone(),
-        };
+        let name = self.current_identifier();
+
+        // Herança opcional: class Filha(Mae):
+        let parent = if self.peek_token_is(TokenType::LParen) {
+            self.next_token();
 
-        // Optional inheritance
-        let parent = if self.peek_token_is(TokenType::Extends) {
-            self.next_token(); // consume 'extends'
-            
             if !self.expect_peek(TokenType::Identifier) {
                 return None;
             }
-            
-            Some(Identifier {
-                token: self.current_token.clone(),
-                value: self.current_token.literal.clone(),
-            })
+
+            let parent = self.current_identifier();
+
+            if !self.expect_peek(TokenType::RParen) {
+                return None;
+            }
+
+            Some(parent)
         } else {
             None
         };
 
-        if !self.expect_peek(TokenType::LBrace) {
+        if !self.expect_peek(TokenType::Colon) {
             return None;
         }
 
-        let mut methods = Vec::new();
-        self.next_token();
+        // O corpo é um bloco comum, na mesma linha (`class A: pass`) ou indentado. Os
+        // métodos são separados das demais instruções, como docstrings e atributos
+        let block = self.parse_block_statement();
+        let span = class_token.span.merge(block.span);
 
-        while !self.current_token_is(TokenType::RBrace) && !self.current_token_is(TokenType::EOF) {
-            if let Some(stmt) = self.parse_method_statement() {
-                if let Statement::Method(method) = stmt {
-                    methods.push(method);
-                }
+        let mut methods = Vec::new();
+        let mut body = Vec::new();
+        for stmt in block.statements {
+            match stmt {
+                Statement::Function(method) => methods.push(method),
+                stmt => body.push(stmt),
             }
-            self.next_token();
         }
 
         Some(Statement::Class(ClassStatement {
+            span,
             token: class_token,
             name,
             parent,
             methods,
+            body,
         }))
     }
 
-    fn parse_method_statement(&mut self) -> Option<Statement> {
+    // Declaração `func nome(parâmetros) -> tipo:` seguida do corpo
+    fn parse_function_statement(&mut self) -> Option<FunctionStatement> {
         let token = self.current_token.clone();
 
-        // Must be an identifier for the method name
-        if !self.current_token_is(TokenType::Identifier) {
+        if !self.expect_peek(TokenType::Identifier) {
             return None;
         }
 
-        let name = Identifier {
-            token: self.current_token.clone(),
-            value: self.current_token.literal.clone(),
-        };
+        let name = self.current_identifier();
 
         if !self.expect_peek(TokenType::LParen) {
             return None;
         }
 
-        let parameters = self.parse_function_parameters();
+        let parameters = self.parse_parameter_list(TokenType::RParen)?;
+        let return_type = self.parse_return_type()?;
 
-        if !self.expect_peek(TokenType::LBrace) {
+        if !self.expect_peek(TokenType::Colon) {
             return None;
         }
 
-        let body = self.parse_block_statement();
+        let body = self.parse_function_body();
 
-        Some(Statement::Method(MethodStatement {
+        Some(FunctionStatement {
+            span: token.span.merge(body.span),
             token,
             name,
             parameters,
+            return_type,
             body,
-        }))
+        })
     }
 
-    fn parse_expression_statement(&mut self) -> Option<Statement> {
-        let stmt = ExpressionStatement {
-            token: self.current_token.clone(),
-            expression: match self.parse_expression(Precedence::Lowest) {
-                Some(expr) => expr,
-                None => return None,
-            },
-        };
+    fn parse_try_statement(&mut self) -> Option<Statement> {
+        let try_token = self.current_token.clone();
 
-        if self.peek_token_is(TokenType::Semicolon) {
-            self.next_token();
+        if !self.expect_peek(TokenType::Colon) {
+            return None;
         }
 
-        Some(Statement::Expression(stmt))
-    }
-
-    fn parse_block_statement(&mut self) -> BlockStatement {
-        let token = self.current_token.clone();
-        let mut statements = Vec::new();
-
-        self.next_token();
+        let try_block = self.parse_block_statement();
+        let mut end = try_block.span;
 
-        while !self.current_token_is(TokenType::RBrace) && !self.current_token_is(TokenType::EOF) {
-            if let Some(stmt) = self.parse_statement() {
-                statements.push(stmt);
-            }
+        let mut except_blocks = Vec::new();
+        while self.peek_token_is(TokenType::Except) {
             self.next_token();
+            let except = self.parse_except_clause()?;
+            end = except.span;
+            except_blocks.push(except);
         }
 
-        BlockStatement { token, statements }
-    }
+        let finally_block = if self.peek_token_is(TokenType::Finally) {
+            self.next_token();
 
-    fn parse_expression(&mut self, precedence: Precedence) -> Option<Expression> {
-        // Try to get a prefix parsing function for the current token
-        let prefix = match self.prefix_parse_fns.get(&self.current_token.token_type) {
-            Some(prefix_fn) => *prefix_fn,
-            None => {
-                self.no_prefix_parse_fn_error(self.current_token.token_type.clone());
+            if !self.expect_peek(TokenType::Colon) {
                 return None;
             }
-        };
-
-        let mut left_exp = prefix(self);
-
-        while !self.peek_token_is(TokenType::Semicolon) && precedence < self.peek_precedence() {
-            // Check if there's an infix parsing function for the peek token
-            let infix = match self.infix_parse_fns.get(&self.peek_token.token_type) {
-                Some(infix_fn) => *infix_fn,
-                None => return left_exp,
-            };
 
-            self.next_token();
+            let block = self.parse_block_statement();
+            end = block.span;
+            Some(block)
+        } else {
+            None
+        };
 
-            left_exp = match left_exp {
-                Some(left) => infix(self, left),
-                None => return None,
-            };
+        if except_blocks.is_empty() && finally_block.is_none() {
+            let msg = format!(
+                "expected except or finally after try block at line {} column {}",
+                self.peek_token.line, self.peek_token.column
+            );
+            self.errors.push(msg);
+            return None;
         }
 
-        left_exp
-    }
-
-    fn no_prefix_parse_fn_error(&mut self, token_type: TokenType) {
-        let msg = format!("no prefix parse function for {:?} found", token_type);
-        self.errors.push(msg);
-    }
-
-    fn parse_identifier(&mut self) -> Option<Expression> {
-        Some(Expression::Identifier(Identifier {
-            token: self.current_token.clone(),
-            value: self.current_token.literal.clone(),
+        Some(Statement::Try(TryStatement {
+            span: try_token.span.merge(end),
+            token: try_token,
+            try_block,
+            except_blocks,
+            finally_block,
         }))
     }
 
-    fn parse_integer_literal(&mut self) -> Option<Expression> {
+    // `except`, `except Tipo:` ou `except Tipo as nome:` a partir do `except` atual
+    fn parse_except_clause(&mut self) -> Option<ExceptClause> {
         let token = self.current_token.clone();
-        
-        let value = match self.current_token.literal.parse::<i64>() {
-            Ok(value) => value,
-            Err(_) => {
-                let msg = format!("could not parse {} as integer", self.current_token.literal);
-                self.errors.push(msg);
+
+        let exception = if self.peek_token_is(TokenType::Colon) {
+            None
+        } else {
+            self.next_token();
+            Some(self.parse_expression(Precedence::Lowest)?)
+        };
+
+        let name = if exception.is_some() && self.peek_token_is(TokenType::As) {
+            self.next_token();
+            if !self.expect_peek(TokenType::Identifier) {
                 return None;
             }
+            Some(self.current_identifier())
+        } else {
+            None
         };
 
-        Some(Expression::IntegerLiteral(IntegerLiteral { token, value }))
-    }
+        if !self.expect_peek(TokenType::Colon) {
+            return None;
+        }
 
-    fn parse_string_literal(&mut self) -> Option<Expression> {
-        Some(Expression::StringLiteral(StringLiteral {
-            token: self.current_token.clone(),
-            value: self.current_token.literal.clone(),
-        }))
-    }
+        let body = self.parse_block_statement();
 
-    fn parse_boolean_literal(&mut self) -> Option<Expression> {
-        Some(Expression::BooleanLiteral(BooleanLiteral {
-            token: self.current_token.clone(),
-            value: self.current_token_is(TokenType::True),
-        }))
+        Some(ExceptClause {
+            span: token.span.merge(body.span),
+            token,
+            exception,
+            name,
+            body,
+        })
     }
 
-    fn parse_prefix_expression(&mut self) -> Option<Expression> {
-        let token = self.current_token.clone();
-        let operator = self.current_token.literal.clone();
+    fn parse_with_statement(&mut self) -> Option<Statement> {
+        let with_token = self.current_token.clone();
+        let mut items = Vec::new();
 
-        self.next_token();
+        loop {
+            self.next_token();
+            let context = self.parse_expression(Precedence::Lowest)?;
+
+            let target = if self.peek_token_is(TokenType::As) {
+                self.next_token();
+                self.next_token();
+                Some(self.parse_target()?)
+            } else {
+                None
+            };
 
-        let right = match self.parse_expression(Precedence::Prefix) {
-            Some(expr) => expr,
-            None => return None,
-        };
+            items.push((context, target));
 
-        Some(Expression::PrefixExpression(PrefixExpression {
-            token,
-            operator,
-            right: Box::new(right),
+            if !self.peek_token_is(TokenType::Comma) {
+                break;
+            }
+            self.next_token();
+        }
+
+        if !self.expect_peek(TokenType::Colon) {
+            return None;
+        }
+
+        let body = self.parse_block_statement();
+
+        Some(Statement::With(WithStatement {
+            span: with_token.span.merge(body.span),
+            token: with_token,
+            items,
+            body,
         }))
     }
 
-    fn parse_infix_expression(&mut self, left: Expression) -> Option<Expression> {
+    // `import a.b as c, d` ou `from .pacote.modulo import a as b, c`
+    fn parse_import_statement(&mut self) -> Option<Statement> {
         let token = self.current_token.clone();
-        let operator = self.current_token.literal.clone();
+        let is_from = token.token_type == TokenType::From;
+        let mut items = Vec::new();
 
-        let precedence = self.current_precedence();
-        self.next_token();
+        let module = if is_from {
+            let module = self.parse_module_path()?;
 
-        let right = match self.parse_expression(precedence) {
-            Some(expr) => expr,
-            None => return None,
+            if !self.expect_peek(TokenType::Import) {
+                return None;
+            }
+
+            if self.peek_token_is(TokenType::Asterisk) {
+                self.next_token();
+                items.push((String::from("*"), None));
+            } else {
+                // A lista de itens pode vir entre parênteses e ocupar várias linhas
+                let parenthesized = self.peek_token_is(TokenType::LParen);
+                if parenthesized {
+                    self.next_token();
+                }
+
+                loop {
+                    if !self.expect_peek(TokenType::Identifier) {
+                        return None;
+                    }
+                    let name = self.current_token.literal.clone();
+                    items.push((name, self.parse_import_alias()?));
+
+                    if !self.peek_token_is(TokenType::Comma) {
+                        break;
+                    }
+                    self.next_token();
+                    if parenthesized && self.peek_token_is(TokenType::RParen) {
+                        break;
+                    }
+                }
+
+                if parenthesized && !self.expect_peek(TokenType::RParen) {
+                    return None;
+                }
+            }
+
+            module
+        } else {
+            loop {
+                if !self.expect_peek(TokenType::Identifier) {
+                    return None;
+                }
+                let name = self.parse_dotted_name()?;
+                items.push((name, self.parse_import_alias()?));
+
+                if !self.peek_token_is(TokenType::Comma) {
+                    break;
+                }
+                self.next_token();
+            }
+
+            items[0].0.clone()
         };
 
-        Some(Expression::InfixExpression(InfixExpression {
-            token,
-            left: Box::new(left),
-            operator,
-            right: Box::new(right),
-        }))
+        let span = self.span_from(token.span);
+
+        if self.peek_token_is(TokenType::Semicolon) {
+            self.next_token();
+        }
+
+        Some(Statement::Import(ImportStatement { token, module, items, is_from, span }))
     }
 
-    fn parse_grouped_expression(&mut self) -> Option<Expression> {
-        self.next_token();
+    // Módulo depois de `from`, com pontos iniciais em importações relativas
+    fn parse_module_path(&mut self) -> Option<String> {
+        let mut path = String::new();
 
-        let exp = self.parse_expression(Precedence::Lowest);
+        while self.peek_token_is(TokenType::Dot) {
+            self.next_token();
+            path.push('.');
+        }
 
-        if !self.expect_peek(TokenType::RParen) {
+        // `from . import x` importa do próprio pacote
+        if !path.is_empty() && self.peek_token_is(TokenType::Import) {
+            return Some(path);
+        }
+
+        if !self.expect_peek(TokenType::Identifier) {
             return None;
         }
 
-        exp
+        path.push_str(&self.parse_dotted_name()?);
+        Some(path)
     }
 
-    fn parse_if_expression(&mut self) -> Option<Expression> {
-        let token = self.current_token.clone();
+    // Nome com pontos, como os.path, a partir do identificador atual
+    fn parse_dotted_name(&mut self) -> Option<String> {
+        let mut name = self.current_token.literal.clone();
 
-        if !self.expect_peek(TokenType::LParen) {
-            return None;
+        while self.peek_token_is(TokenType::Dot) {
+            self.next_token();
+            if !self.expect_peek(TokenType::Identifier) {
+                return None;
+            }
+            name.push('.');
+            name.push_str(&self.current_token.literal);
+        }
+
+        Some(name)
+    }
+
+    // `as nome` opcional depois de um item importado. Devolve None em caso de erro
+    fn parse_import_alias(&mut self) -> Option<Option<String>> {
+        if !self.peek_token_is(TokenType::As) {
+            return Some(None);
         }
 
         self.next_token();
-        let condition = match self.parse_expression(Precedence::Lowest) {
-            Some(expr) => expr,
-            None => return None,
+        if !self.expect_peek(TokenType::Identifier) {
+            return None;
+        }
+
+        Some(Some(self.current_token.literal.clone()))
+    }
+
+    // `raise`, `raise Erro("msg")` ou `raise Erro("msg") from causa`
+    fn parse_raise_statement(&mut self) -> Option<Statement> {
+        let token = self.current_token.clone();
+
+        let exception = if self.peek_ends_statement() {
+            None
+        } else {
+            self.next_token();
+            Some(self.parse_expression(Precedence::Lowest)?)
         };
 
-        if !self.expect_peek(TokenType::RParen) {
-            return None;
+        let cause = if exception.is_some() && self.peek_token_is(TokenType::From) {
+            self.next_token();
+            self.next_token();
+            Some(self.parse_expression(Precedence::Lowest)?)
+        } else {
+            None
+        };
+
+        let span = self.span_from(token.span);
+
+        if self.peek_token_is(TokenType::Semicolon) {
+            self.next_token();
         }
 
-        if !self.expect_peek(TokenType::LBrace) {
+        Some(Statement::Raise(RaiseStatement { token, exception, cause, span }))
+    }
+
+    fn parse_loop_control_statement(&mut self) -> Option<Statement> {
+        let token = self.current_token.clone();
+
+        if self.loop_depth == 0 {
+            let msg = format!(
+                "'{}' outside loop at line {} column {}",
+                token.literal, token.line, token.column
+            );
+            self.errors.push(msg);
             return None;
         }
 
-        let consequence = self.parse_block_statement();
+        if self.peek_token_is(TokenType::Semicolon) {
+            self.next_token();
+        }
+
+        let span = token.span;
+        Some(match token.token_type {
+            TokenType::Break => Statement::Break(BreakStatement { token, span }),
+            _ => Statement::Continue(ContinueStatement { token, span }),
+        })
+    }
+
+    fn parse_pass_statement(&mut self) -> Option<Statement> {
+        let token = self.current_token.clone();
+
+        if self.peek_token_is(TokenType::Semicolon) {
+            self.next_token();
+        }
+
+        Some(Statement::Pass(PassStatement { span: token.span, token }))
+    }
+
+    fn parse_expression_statement(&mut self) -> Option<Statement> {
+        let token = self.current_token.clone();
+        let expression = self.parse_assignment_statement()?;
+
+        if self.peek_token_is(TokenType::Semicolon) {
+            self.next_token();
+        }
+
+        Some(Statement::Expression(ExpressionStatement {
+            span: token.span.merge(expression.span()),
+            token,
+            expression: Some(expression),
+        }))
+    }
+
+    // Analisa o corpo de um bloco a partir do ':' atual. O corpo pode estar na
+    // mesma linha (`if x: y`) ou nas linhas seguintes, delimitado por INDENT/DEDENT.
+    // Ao final, o token atual é o NEWLINE ou DEDENT que encerra o bloco.
+    fn parse_block_statement(&mut self) -> BlockStatement {
+        let token = self.current_token.clone();
+        let mut statements = Vec::new();
+
+        if !self.peek_token_is(TokenType::Newline) {
+            self.next_token();
+            if let Some(stmt) = self.parse_statement() {
+                statements.push(stmt);
+            }
+            if self.peek_token_is(TokenType::Newline) {
+                self.next_token();
+            }
+            return BlockStatement { span: Self::block_span(&token, &statements), token, statements };
+        }
+
+        self.next_token();
+        if !self.expect_peek(TokenType::Indent) {
+            return BlockStatement { span: Self::block_span(&token, &statements), token, statements };
+        }
+        self.next_token();
+
+        while !self.current_token_is(TokenType::Dedent) && !self.current_token_is(TokenType::Eof) {
+            if self.current_token_is(TokenType::Newline) {
+                self.next_token();
+                continue;
+            }
+
+            if let Some(stmt) = self.parse_statement() {
+                statements.push(stmt);
+            }
+            self.next_token();
+        }
+
+        BlockStatement { span: Self::block_span(&token, &statements), token, statements }
+    }
+
+    fn parse_expression(&mut self, precedence: Precedence) -> Option<Expression> {
+        // Try to get a prefix parsing function for the current token
+        let prefix = match self.prefix_parse_fns.get(&self.current_token.token_type) {
+            Some(prefix_fn) => *prefix_fn,
+            None => {
+                self.no_prefix_parse_fn_error();
+                return None;
+            }
+        };
+
+        let mut left_exp = prefix(self);
+
+        while !self.peek_token_is(TokenType::Semicolon) && precedence < self.peek_precedence() {
+            // Check if there's an infix parsing function for the peek token
+            let infix = match self.infix_parse_fns.get(&self.peek_token.token_type) {
+                Some(infix_fn) => *infix_fn,
+                None => return left_exp,
+            };
 
-        let alternative = if self.peek_token_is(TokenType::Else) {
             self.next_token();
 
-            if !self.expect_peek(TokenType::LBrace) {
+            left_exp = match left_exp {
+                Some(left) => infix(self, left),
+                None => return None,
+            };
+        }
+
+        left_exp
+    }
+
+    fn no_prefix_parse_fn_error(&mut self) {
+        let msg = format!(
+            "no prefix parse function for {:?} found at line {} column {}",
+            self.current_token.token_type, self.current_token.line, self.current_token.column
+        );
+        self.errors.push(msg);
+    }
+
+    fn parse_identifier(&mut self) -> Option<Expression> {
+        Some(Expression::Identifier(self.current_identifier()))
+    }
+
+    fn parse_integer_literal(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+
+        let value = match int_literal_value(&self.current_token.literal) {
+            Some(value) => value,
+            None => {
+                let msg = format!("could not parse {} as integer", self.current_token.literal);
+                self.errors.push(msg);
+                return None;
+            }
+        };
+
+        Some(Expression::IntegerLiteral(IntegerLiteral { span: token.span, token, value }))
+    }
+
+    fn parse_float_literal(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+
+        let value = match float_literal_value(&self.current_token.literal) {
+            Some(value) => value,
+            None => {
+                let msg = format!("could not parse {} as float", self.current_token.literal);
+                self.errors.push(msg);
                 return None;
             }
+        };
+
+        Some(Expression::FloatLiteral(FloatLiteral { span: token.span, token, value }))
+    }
+
+    fn parse_imaginary_literal(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+
+        let value = match float_literal_value(&self.current_token.literal) {
+            Some(value) => value,
+            None => {
+                let msg = format!("could not parse {} as imaginary number", self.current_token.literal);
+                self.errors.push(msg);
+                return None;
+            }
+        };
+
+        Some(Expression::ImaginaryLiteral(ImaginaryLiteral { span: token.span, token, value }))
+    }
+
+    fn parse_string_literal(&mut self) -> Option<Expression> {
+        Some(Expression::StringLiteral(StringLiteral {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.clone(),
+            span: self.current_token.span,
+        }))
+    }
+
+    fn parse_bytes_literal(&mut self) -> Option<Expression> {
+        // O lexer representa cada byte como um caractere entre U+0000 e U+00FF
+        Some(Expression::BytesLiteral(BytesLiteral {
+            token: self.current_token.clone(),
+            value: self.current_token.literal.chars().map(|ch| ch as u8).collect(),
+            span: self.current_token.span,
+        }))
+    }
+
+    fn parse_fstring_literal(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let mut parts = Vec::new();
+
+        self.next_token();
+
+        while !self.current_token_is(TokenType::FStringEnd) {
+            match self.current_token.token_type {
+                TokenType::FStringMiddle => {
+                    parts.push(FStringPart::Literal(self.current_token.literal.clone()));
+                }
+                TokenType::LBrace => self.parse_fstring_field(&mut parts)?,
+                _ => {
+                    let msg = format!(
+                        "unexpected {:?} inside f-string",
+                        self.current_token.token_type
+                    );
+                    self.errors.push(msg);
+                    return None;
+                }
+            }
 
-            Some(self.parse_block_statement())
+            self.next_token();
+        }
+
+        Some(Expression::FString(FStringLiteral { span: self.span_from(token.span), token, parts }))
+    }
+
+    // Campo `{expressão=!conversão:formato}` a partir do '{' atual, até o '}'. No campo de
+    // depuração `{x=}` o lexer emite o texto "x=" como FSTRING_MIDDLE depois da expressão:
+    // ele entra nos trechos antes do campo e, sem conversão nem formato, o valor usa repr
+    fn parse_fstring_field(&mut self, parts: &mut Vec<FStringPart>) -> Option<()> {
+        self.next_token();
+        let expression = self.parse_expression(Precedence::Lowest)?;
+
+        let debug_text = if self.peek_token_is(TokenType::FStringMiddle) {
+            self.next_token();
+            Some(self.current_token.literal.clone())
         } else {
             None
         };
 
-        Some(Expression::IfExpression(IfExpression {
-            token,
-            condition: Box::new(condition),
-            consequence,
-            alternative,
+        let mut conversion = if self.peek_token_is(TokenType::Bang) {
+            self.next_token();
+            if !self.expect_peek(TokenType::Identifier) {
+                return None;
+            }
+            self.current_token.literal.chars().next()
+        } else {
+            None
+        };
+
+        let mut format_spec = Vec::new();
+        if self.peek_token_is(TokenType::Colon) {
+            self.next_token();
+            while !self.peek_token_is(TokenType::RBrace) {
+                self.next_token();
+                match self.current_token.token_type {
+                    TokenType::FStringMiddle => {
+                        format_spec.push(FStringPart::Literal(self.current_token.literal.clone()));
+                    }
+                    TokenType::LBrace => self.parse_fstring_field(&mut format_spec)?,
+                    _ => {
+                        let msg = format!(
+                            "unexpected {:?} inside f-string",
+                            self.current_token.token_type
+                        );
+                        self.errors.push(msg);
+                        return None;
+                    }
+                }
+            }
+        }
+
+        if !self.expect_peek(TokenType::RBrace) {
+            return None;
+        }
+
+        if let Some(debug_text) = debug_text {
+            if conversion.is_none() && format_spec.is_empty() {
+                conversion = Some('r');
+            }
+            match parts.last_mut() {
+                Some(FStringPart::Literal(text)) => text.push_str(&debug_text),
+                _ => parts.push(FStringPart::Literal(debug_text)),
+            }
+        }
+
+        parts.push(FStringPart::Field {
+            expression: Box::new(expression),
+            conversion,
+            format_spec,
+        });
+        Some(())
+    }
+
+    fn parse_boolean_literal(&mut self) -> Option<Expression> {
+        Some(Expression::Boolean(Boolean {
+            token: self.current_token.clone(),
+            value: self.current_token_is(TokenType::True),
+            span: self.current_token.span,
         }))
     }
 
-    fn parse_function_literal(&mut self) -> Option<Expression> {
-        let token = self.current_token.clone();
+    fn parse_none_literal(&mut self) -> Option<Expression> {
+        Some(Expression::None(NoneLiteral {
+            token: self.current_token.clone(),
+            span: self.current_token.span,
+        }))
+    }
+
+    fn parse_prefix_expression(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let operator = self.current_token.literal.clone();
+
+        // `not` se aplica a comparações inteiras: not a == b equivale a not (a == b)
+        let precedence = if token.token_type == TokenType::Not { Precedence::Not } else { Precedence::Prefix };
+        self.next_token();
+
+        let right = self.parse_expression(precedence)?;
+
+        Some(Expression::Prefix(PrefixExpression {
+            span: self.span_from(token.span),
+            token,
+            operator,
+            right: Box::new(right),
+        }))
+    }
+
+    fn parse_infix_expression(&mut self, left: Expression) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let operator = self.current_token.literal.clone();
+
+        // `**` associa à direita e aceita um operando prefixado: 2 ** -x ** 2 equivale a 2 ** (-(x ** 2))
+        let precedence = match token.token_type {
+            TokenType::Power => Precedence::Prefix,
+            _ => self.current_precedence(),
+        };
+        self.next_token();
+
+        let right = self.parse_expression(precedence)?;
+
+        Some(Expression::Infix(InfixExpression {
+            span: self.span_from(left.span()),
+            token,
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        }))
+    }
+
+    // Comparações, incluindo `in`, `not in`, `is` e `is not`. Uma comparação simples vira
+    // uma InfixExpression; duas ou mais encadeadas, como a < b <= c, uma ComparisonExpression
+    fn parse_comparison_expression(&mut self, left: Expression) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let mut comparisons = Vec::new();
+
+        loop {
+            let operator = self.parse_comparison_operator()?;
+            self.next_token();
+            let right = self.parse_expression(Precedence::Comparison)?;
+            comparisons.push((operator, right));
+
+            if self.peek_precedence() != Precedence::Comparison {
+                break;
+            }
+            self.next_token();
+        }
+
+        if comparisons.len() == 1 {
+            let (operator, right) = comparisons.remove(0);
+            return Some(Expression::Infix(InfixExpression {
+                span: self.span_from(left.span()),
+                token,
+                left: Box::new(left),
+                operator,
+                right: Box::new(right),
+            }));
+        }
+
+        Some(Expression::Comparison(ComparisonExpression {
+            span: self.span_from(left.span()),
+            token,
+            left: Box::new(left),
+            comparisons,
+        }))
+    }
+
+    // Operador de comparação a partir do token atual, juntando `not in` e `is not`
+    fn parse_comparison_operator(&mut self) -> Option<String> {
+        match self.current_token.token_type {
+            TokenType::Not => {
+                if !self.expect_peek(TokenType::In) {
+                    return None;
+                }
+                Some(String::from("not in"))
+            }
+            TokenType::Is if self.peek_token_is(TokenType::Not) => {
+                self.next_token();
+                Some(String::from("is not"))
+            }
+            _ => Some(self.current_token.literal.clone()),
+        }
+    }
+
+    fn parse_logical_expression(&mut self, left: Expression) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let operator = self.current_token.literal.clone();
+
+        let precedence = self.current_precedence();
+        self.next_token();
+
+        let right = self.parse_expression(precedence)?;
+
+        Some(Expression::Logical(LogicalExpression {
+            span: self.span_from(left.span()),
+            token,
+            left: Box::new(left),
+            operator,
+            right: Box::new(right),
+        }))
+    }
+
+    // nome := valor
+    fn parse_walrus_expression(&mut self, left: Expression) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let operator = self.current_token.literal.clone();
+
+        // `:=` só pode atribuir a um nome
+        if !matches!(left, Expression::Identifier(_)) {
+            let msg = format!(
+                "cannot use := with a target that is not an identifier at line {} column {}",
+                token.line, token.column
+            );
+            self.errors.push(msg);
+            return None;
+        }
+
+        self.next_token();
+
+        // O valor não inclui outro `:=`: a := b := c não é aceito sem parênteses
+        let value = self.parse_expression(Precedence::Assignment)?;
+
+        Some(Expression::Assignment(AssignmentExpression {
+            span: self.span_from(left.span()),
+            token,
+            left: Box::new(left),
+            value: Box::new(value),
+            operator,
+        }))
+    }
+
+    // Expressão de um statement, que pode ser uma atribuição com `=` ou um operador
+    // aumentado. Alvos e valores podem ser tuplas sem parênteses: `a, b = b, a` ou
+    // `primeiro, *resto = itens`
+    fn parse_assignment_statement(&mut self) -> Option<Expression> {
+        let left = self.parse_tuple_expression(Precedence::Assignment)?;
+
+        if !self.check_unparenthesized_walrus() {
+            return None;
+        }
+
+        if !self.peek_is_assignment_operator() {
+            return Some(left);
+        }
+
+        self.next_token();
+        self.parse_assignment(left)
+    }
+
+    // Atribuição a partir do operador atual. Só `=` se encadeia: a = b = c equivale a
+    // a = (b = c)
+    fn parse_assignment(&mut self, left: Expression) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let operator = self.current_token.literal.clone();
+
+        if !self.check_assignment_target(&left, &token) {
+            return None;
+        }
+
+        self.next_token();
+        let mut value = self.parse_tuple_expression(Precedence::Assignment)?;
+
+        if !self.check_unparenthesized_walrus() {
+            return None;
+        }
+
+        if token.token_type == TokenType::Assign && self.peek_token_is(TokenType::Assign) {
+            self.next_token();
+            value = self.parse_assignment(value)?;
+        }
+
+        Some(Expression::Assignment(AssignmentExpression {
+            span: self.span_from(left.span()),
+            token,
+            left: Box::new(left),
+            value: Box::new(value),
+            operator,
+        }))
+    }
+
+    // Como em Python, `:=` sem parênteses não forma um statement nem o valor de uma
+    // atribuição: `n := 1` e `x = y := 2` precisam ser escritos `(n := 1)` e `x = (y := 2)`
+    fn check_unparenthesized_walrus(&mut self) -> bool {
+        if !self.peek_token_is(TokenType::Walrus) {
+            return true;
+        }
+
+        let msg = format!(
+            "cannot use := without parentheses here at line {} column {}",
+            self.peek_token.line, self.peek_token.column
+        );
+        self.errors.push(msg);
+        self.skip_statement();
+        false
+    }
+
+    fn peek_is_assignment_operator(&self) -> bool {
+        matches!(
+            self.peek_token.token_type,
+            TokenType::Assign
+                | TokenType::PlusAssign
+                | TokenType::MinusAssign
+                | TokenType::StarAssign
+                | TokenType::SlashAssign
+                | TokenType::FloorDivAssign
+                | TokenType::PercentAssign
+                | TokenType::PowerAssign
+                | TokenType::AtAssign
+                | TokenType::AmpersandAssign
+                | TokenType::PipeAssign
+                | TokenType::CaretAssign
+                | TokenType::ShiftLeftAssign
+                | TokenType::ShiftRightAssign
+        )
+    }
+
+    // Só nomes, atributos e índices recebem valores; tuplas e listas, com no máximo um
+    // alvo desempacotado (*resto), apenas com `=`. Registra o erro e devolve false caso contrário
+    fn check_assignment_target(&mut self, target: &Expression, token: &Token) -> bool {
+        let error = match target {
+            Expression::Identifier(_) | Expression::Attribute(_) | Expression::Index(_) => return true,
+            Expression::Tuple(TupleLiteral { elements, .. }) | Expression::Array(ArrayLiteral { elements, .. })
+                if token.token_type == TokenType::Assign =>
+            {
+                let starred = elements.iter().filter(|e| matches!(e, Expression::Starred(_))).count();
+                if starred <= 1 {
+                    return elements.iter().all(|element| match element {
+                        Expression::Starred(starred) => self.check_assignment_target(&starred.value, token),
+                        element => self.check_assignment_target(element, token),
+                    });
+                }
+                String::from("multiple starred expressions in assignment")
+            }
+            Expression::Starred(_) if token.token_type == TokenType::Assign => {
+                String::from("starred assignment target must be in a list or tuple")
+            }
+            _ => format!("cannot assign to {}", target.string()),
+        };
+
+        self.errors.push(format!("{} at line {} column {}", error, token.line, token.column));
+        false
+    }
+
+    // Expressão seguida opcionalmente de vírgulas. Mais de um elemento, ou uma vírgula
+    // final, formam uma tupla sem parênteses: `1, 2` ou `x,`
+    fn parse_tuple_expression(&mut self, precedence: Precedence) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let first = self.parse_tuple_element(precedence)?;
+
+        if !self.peek_token_is(TokenType::Comma) {
+            // `*a = xs` chega até `check_assignment_target`, que explica o erro
+            if matches!(first, Expression::Starred(_)) && !self.peek_is_assignment_operator() {
+                self.starred_expression_error(&token);
+                return None;
+            }
+            return Some(first);
+        }
+
+        let mut elements = vec![first];
+        while self.peek_token_is(TokenType::Comma) {
+            self.next_token();
+            if !self.prefix_parse_fns.contains_key(&self.peek_token.token_type)
+                && !self.peek_token_is(TokenType::Asterisk)
+            {
+                break;
+            }
+            self.next_token();
+            elements.push(self.parse_tuple_element(precedence)?);
+        }
+
+        Some(Expression::Tuple(TupleLiteral { span: self.span_from(token.span), token, elements }))
+    }
+
+    // Elemento de uma tupla, lista ou lista de alvos: uma expressão ou um *valor
+    // desempacotado, que não é aceito fora desses contextos
+    fn parse_tuple_element(&mut self, precedence: Precedence) -> Option<Expression> {
+        if self.current_token_is(TokenType::Asterisk) {
+            return self.parse_starred_expression();
+        }
+        self.parse_expression(precedence)
+    }
+
+    fn starred_expression_error(&mut self, token: &Token) {
+        let msg = format!("cannot use starred expression here at line {} column {}", token.line, token.column);
+        self.errors.push(msg);
+        self.skip_statement();
+    }
+
+    // *valor: desempacota um iterável em uma tupla, lista ou alvo de atribuição
+    fn parse_starred_expression(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+        self.next_token();
+
+        let value = self.parse_expression(Precedence::Comparison)?;
+
+        Some(Expression::Starred(StarredExpression {
+            span: self.span_from(token.span),
+            token,
+            value: Box::new(value),
+        }))
+    }
+
+    fn parse_grouped_expression(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+
+        // (): tupla vazia
+        if self.peek_token_is(TokenType::RParen) {
+            self.next_token();
+            return Some(Expression::Tuple(TupleLiteral {
+                span: self.span_from(token.span),
+                token,
+                elements: Vec::new(),
+            }));
+        }
+
+        self.next_token();
+        let first_token = self.current_token.clone();
+        let exp = self.parse_tuple_element(Precedence::Lowest)?;
+
+        // (1, 2) ou (1,): a vírgula forma a tupla
+        if self.peek_token_is(TokenType::Comma) {
+            self.next_token();
+            let mut elements = vec![exp];
+            elements.extend(self.parse_expression_list(TokenType::RParen)?);
+
+            return Some(Expression::Tuple(TupleLiteral { span: self.span_from(token.span), token, elements }));
+        }
+
+        // (*a) não forma uma tupla
+        if matches!(exp, Expression::Starred(_)) {
+            self.starred_expression_error(&first_token);
+            return None;
+        }
+
+        // (x for x in xs): gerador entre parênteses
+        if self.peek_token_is(TokenType::For) {
+            let clauses = self.parse_comprehension_clauses()?;
+
+            if !self.expect_peek(TokenType::RParen) {
+                return None;
+            }
+
+            return Some(Expression::Generator(GeneratorExpression {
+                span: self.span_from(token.span),
+                token,
+                element: Box::new(exp),
+                scope: ComprehensionScope::new(&clauses),
+                clauses,
+            }));
+        }
+
+        if !self.expect_peek(TokenType::RParen) {
+            return None;
+        }
+
+        Some(exp)
+    }
+
+    // Função anônima: func(parâmetros) -> tipo: corpo
+    fn parse_function_literal(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenType::LParen) {
+            return None;
+        }
+
+        let parameters = self.parse_parameter_list(TokenType::RParen)?;
+        let return_type = self.parse_return_type()?.map(Box::new);
+
+        if !self.expect_peek(TokenType::Colon) {
+            return None;
+        }
+
+        let body = self.parse_function_body();
+
+        Some(Expression::FunctionLiteral(FunctionLiteral {
+            span: token.span.merge(body.span),
+            token,
+            parameters,
+            return_type,
+            body,
+        }))
+    }
+
+    // Anotação opcional `-> tipo` depois dos parâmetros. Devolve None em caso de erro
+    fn parse_return_type(&mut self) -> Option<Option<Expression>> {
+        if !self.peek_token_is(TokenType::Arrow) {
+            return Some(None);
+        }
+
+        self.next_token();
+        self.next_token();
+        self.parse_expression(Precedence::Lowest).map(Some)
+    }
+
+    // Parâmetros separados por vírgula até `end`, que é consumido: `a, b=1, *args, c,
+    // **kwargs`. Nomes repetidos e parâmetros fora de ordem são registrados como erros
+    fn parse_parameter_list(&mut self, end: TokenType) -> Option<Vec<Parameter>> {
+        let mut parameters: Vec<Parameter> = Vec::new();
+        // Depois de *args ou de um * isolado, os parâmetros só podem ser passados por nome
+        let mut keyword_only = false;
+        let mut bare_star: Option<Token> = None;
+
+        while !self.peek_token_is(end) {
+            let token = self.peek_token.clone();
+
+            if let Some(last) = parameters.last() {
+                if last.kind == ParameterKind::VarKeyword {
+                    self.errors.push(format!(
+                        "arguments cannot follow var-keyword argument at line {} column {}",
+                        token.line, token.column
+                    ));
+                }
+            }
+
+            let kind = match token.token_type {
+                TokenType::Asterisk => {
+                    self.next_token();
+                    if keyword_only {
+                        self.errors.push(format!(
+                            "* argument may appear only once at line {} column {}",
+                            token.line, token.column
+                        ));
+                    }
+                    keyword_only = true;
+
+                    // `*` isolado: apenas separa os parâmetros só por nome
+                    if self.peek_token_is(TokenType::Comma) || self.peek_token_is(end) {
+                        bare_star = Some(token);
+                        if self.peek_token_is(TokenType::Comma) {
+                            self.next_token();
+                        }
+                        continue;
+                    }
+
+                    ParameterKind::VarPositional
+                }
+                TokenType::Power => {
+                    self.next_token();
+                    ParameterKind::VarKeyword
+                }
+                _ if keyword_only => ParameterKind::KeywordOnly,
+                _ => ParameterKind::Positional,
+            };
+
+            if !self.expect_peek(TokenType::Identifier) {
+                return None;
+            }
+            let name = self.current_identifier();
+
+            let default = if self.peek_token_is(TokenType::Assign) {
+                self.next_token();
+                self.next_token();
+                Some(self.parse_expression(Precedence::Lowest)?)
+            } else {
+                None
+            };
+
+            if let Some(bare) = bare_star.take() {
+                if kind != ParameterKind::KeywordOnly {
+                    self.errors.push(format!(
+                        "named arguments must follow bare * at line {} column {}",
+                        bare.line, bare.column
+                    ));
+                }
+            }
+
+            self.check_parameter(&parameters, &name, kind, default.is_some());
+
+            parameters.push(Parameter {
+                span: self.span_from(token.span),
+                token,
+                name,
+                kind,
+                default,
+            });
+
+            if !self.peek_token_is(end) && !self.expect_peek(TokenType::Comma) {
+                return None;
+            }
+        }
+
+        if let Some(bare) = bare_star {
+            self.errors.push(format!(
+                "named arguments must follow bare * at line {} column {}",
+                bare.line, bare.column
+            ));
+        }
+
+        self.next_token();
+        Some(parameters)
+    }
+
+    // Registra erros para um parâmetro com nome repetido, para valores padrão em
+    // *args ou **kwargs e para um parâmetro sem valor padrão depois de outro com
+    fn check_parameter(&mut self, parameters: &[Parameter], name: &Identifier, kind: ParameterKind, has_default: bool) {
+        let token = &name.token;
+
+        if parameters.iter().any(|p| p.name.value == name.value) {
+            self.errors.push(format!(
+                "duplicate argument '{}' in function definition at line {} column {}",
+                name.value, token.line, token.column
+            ));
+        }
+
+        match kind {
+            ParameterKind::VarPositional | ParameterKind::VarKeyword if has_default => {
+                self.errors.push(format!(
+                    "var-positional and var-keyword arguments cannot have default value at line {} column {}",
+                    token.line, token.column
+                ));
+            }
+            ParameterKind::Positional if !has_default => {
+                let follows_default = parameters
+                    .iter()
+                    .any(|p| p.kind == ParameterKind::Positional && p.default.is_some());
+                if follows_default {
+                    self.errors.push(format!(
+                        "non-default argument follows default argument at line {} column {}",
+                        token.line, token.column
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // lambda a, b=1, *args: expressão
+    fn parse_lambda_expression(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let parameters = self.parse_parameter_list(TokenType::Colon)?;
+
+        self.next_token();
+        let body = self.parse_expression(Precedence::Lowest)?;
+
+        Some(Expression::Lambda(LambdaExpression {
+            span: self.span_from(token.span),
+            token,
+            parameters,
+            body: Box::new(body),
+        }))
+    }
+
+    // `yield`, `yield valor` ou `yield from iterável`
+    fn parse_yield_expression(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+
+        let is_from = self.peek_token_is(TokenType::From);
+        if is_from {
+            self.next_token();
+        }
+
+        let value = if !is_from && (self.peek_ends_statement() || self.peek_token_is(TokenType::RParen)) {
+            None
+        } else {
+            self.next_token();
+            Some(Box::new(self.parse_expression(Precedence::Lowest)?))
+        };
+
+        Some(Expression::Yield(YieldExpression {
+            span: self.span_from(token.span),
+            token,
+            value,
+            is_from,
+        }))
+    }
+
+    fn parse_call_expression(&mut self, function: Expression) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let arguments = self.parse_call_arguments()?;
+
+        Some(Expression::Call(CallExpression {
+            span: self.span_from(function.span()),
+            token,
+            function: Box::new(function),
+            arguments,
+        }))
+    }
+
+    // Argumentos de uma chamada até o ')': posicionais, por nome (c=2) e desempacotados
+    // (*xs, **opts). Um gerador dispensa os próprios parênteses quando é o único
+    // argumento: sum(x for x in xs)
+    fn parse_call_arguments(&mut self) -> Option<Vec<Argument>> {
+        let mut arguments: Vec<Argument> = Vec::new();
+
+        while !self.peek_token_is(TokenType::RParen) {
+            self.next_token();
+            let argument = self.parse_argument()?;
+
+            if arguments.is_empty()
+                && matches!(argument.kind, ArgumentKind::Positional)
+                && self.peek_token_is(TokenType::For)
+            {
+                let clauses = self.parse_comprehension_clauses()?;
+                let span = self.span_from(argument.span);
+                let generator = GeneratorExpression {
+                    span,
+                    token: argument.token.clone(),
+                    element: Box::new(argument.value),
+                    scope: ComprehensionScope::new(&clauses),
+                    clauses,
+                };
+
+                if !self.expect_peek(TokenType::RParen) {
+                    return None;
+                }
+
+                return Some(vec![Argument {
+                    span,
+                    token: argument.token,
+                    kind: ArgumentKind::Positional,
+                    value: Expression::Generator(generator),
+                }]);
+            }
+
+            self.check_argument(&arguments, &argument);
+            arguments.push(argument);
+
+            if !self.peek_token_is(TokenType::RParen) && !self.expect_peek(TokenType::Comma) {
+                return None;
+            }
+        }
+
+        self.next_token();
+        Some(arguments)
+    }
+
+    // Um argumento a partir do token atual
+    fn parse_argument(&mut self) -> Option<Argument> {
+        let token = self.current_token.clone();
+
+        let kind = match token.token_type {
+            TokenType::Asterisk => ArgumentKind::Unpack,
+            TokenType::Power => ArgumentKind::UnpackKeywords,
+            TokenType::Identifier if self.peek_token_is(TokenType::Assign) => {
+                let name = self.current_identifier();
+                self.next_token();
+                ArgumentKind::Keyword(name)
+            }
+            _ => ArgumentKind::Positional,
+        };
+
+        if !matches!(kind, ArgumentKind::Positional) {
+            self.next_token();
+        }
+
+        let value = self.parse_expression(Precedence::Lowest)?;
+
+        // f(a.b=1): só nomes podem ser passados como argumentos nomeados
+        if self.peek_token_is(TokenType::Assign) {
+            let msg = format!(
+                "expression cannot contain assignment, perhaps you meant \"==\"? at line {} column {}",
+                self.peek_token.line, self.peek_token.column
+            );
+            self.errors.push(msg);
+            return None;
+        }
+
+        Some(Argument { span: self.span_from(token.span), token, kind, value })
+    }
+
+    // Registra erros para argumentos fora de ordem e nomes repetidos: posicionais e *xs
+    // não podem vir depois de **opts, e posicionais não podem vir depois de c=2
+    fn check_argument(&mut self, arguments: &[Argument], argument: &Argument) {
+        let token = &argument.token;
+        let after_keyword = arguments.iter().any(|a| matches!(a.kind, ArgumentKind::Keyword(_)));
+        let after_unpack_keywords = arguments.iter().any(|a| matches!(a.kind, ArgumentKind::UnpackKeywords));
+
+        let message = match &argument.kind {
+            ArgumentKind::Positional if after_unpack_keywords => {
+                "positional argument follows keyword argument unpacking".to_string()
+            }
+            ArgumentKind::Positional if after_keyword => "positional argument follows keyword argument".to_string(),
+            ArgumentKind::Unpack if after_unpack_keywords => {
+                "iterable argument unpacking follows keyword argument unpacking".to_string()
+            }
+            ArgumentKind::Keyword(name) => {
+                let repeated = arguments
+                    .iter()
+                    .any(|a| matches!(&a.kind, ArgumentKind::Keyword(other) if other.value == name.value));
+                if !repeated {
+                    return;
+                }
+                format!("keyword argument repeated: {}", name.value)
+            }
+            _ => return,
+        };
+
+        self.errors.push(format!("{} at line {} column {}", message, token.line, token.column));
+    }
+
+    fn parse_array_literal(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+
+        if self.peek_token_is(TokenType::RBracket) {
+            self.next_token();
+            return Some(Expression::Array(ArrayLiteral {
+                span: self.span_from(token.span),
+                token,
+                elements: Vec::new(),
+            }));
+        }
+
+        self.next_token();
+        let first_token = self.current_token.clone();
+        let first = self.parse_tuple_element(Precedence::Lowest)?;
+
+        // [x * 2 for x in xs if x > 0]
+        if self.peek_token_is(TokenType::For) {
+            if matches!(first, Expression::Starred(_)) {
+                self.starred_expression_error(&first_token);
+                return None;
+            }
+
+            let clauses = self.parse_comprehension_clauses()?;
+
+            if !self.expect_peek(TokenType::RBracket) {
+                return None;
+            }
+
+            return Some(Expression::ListComprehension(ListComprehension {
+                span: self.span_from(token.span),
+                token,
+                element: Box::new(first),
+                scope: ComprehensionScope::new(&clauses),
+                clauses,
+            }));
+        }
+
+        if !self.peek_token_is(TokenType::RBracket) && !self.expect_peek(TokenType::Comma) {
+            return None;
+        }
+
+        let mut elements = vec![first];
+        elements.extend(self.parse_expression_list(TokenType::RBracket)?);
+
+        Some(Expression::Array(ArrayLiteral {
+            span: self.span_from(token.span),
+            token,
+            elements,
+        }))
+    }
+
+    // Cláusulas `for alvos in iterável` de uma compreensão, cada uma seguida de zero
+    // ou mais filtros `if condição`. O token atual é o último da expressão produzida
+    fn parse_comprehension_clauses(&mut self) -> Option<Vec<ComprehensionClause>> {
+        let mut clauses = Vec::new();
+
+        while self.peek_token_is(TokenType::For) {
+            self.next_token();
+            let token = self.current_token.clone();
+
+            self.next_token();
+            let target = self.parse_target_list(TokenType::In)?;
+
+            if !self.expect_peek(TokenType::In) {
+                return None;
+            }
+
+            // Atribuições sem parênteses não são aceitas no iterável nem nos filtros
+            self.next_token();
+            let iterable = self.parse_expression(Precedence::Assignment)?;
+
+            let mut conditions = Vec::new();
+            while self.peek_token_is(TokenType::If) {
+                self.next_token();
+                self.next_token();
+                conditions.push(self.parse_expression(Precedence::Assignment)?);
+            }
+
+            clauses.push(ComprehensionClause {
+                span: self.span_from(token.span),
+                token,
+                target,
+                iterable,
+                conditions,
+            });
+        }
+
+        Some(clauses)
+    }
+
+    // Elementos de uma tupla ou lista separados por vírgula até `end`, aceitando uma
+    // vírgula final
+    fn parse_expression_list(&mut self, end: TokenType) -> Option<Vec<Expression>> {
+        let mut list = Vec::new();
+
+        while !self.peek_token_is(end) {
+            self.next_token();
+            list.push(self.parse_tuple_element(Precedence::Lowest)?);
+
+            if !self.peek_token_is(end) && !self.expect_peek(TokenType::Comma) {
+                return None;
+            }
+        }
+
+        self.next_token();
+        Some(list)
+    }
+
+    fn parse_index_expression(&mut self, left: Expression) -> Option<Expression> {
+        let token = self.current_token.clone();
+
+        self.next_token();
+        let index = self.parse_subscript_list()?;
+
+        if !self.expect_peek(TokenType::RBracket) {
+            return None;
+        }
+
+        Some(Expression::Index(IndexExpression {
+            span: self.span_from(left.span()),
+            token,
+            left: Box::new(left),
+            index: Box::new(index),
+        }))
+    }
+
+    // Índices separados por vírgula a partir do token atual; mais de um, ou uma
+    // vírgula final, formam uma tupla: m[i, j] equivale a m[(i, j)]
+    fn parse_subscript_list(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let first = self.parse_subscript()?;
+
+        if !self.peek_token_is(TokenType::Comma) {
+            return Some(first);
+        }
+
+        let mut elements = vec![first];
+        while self.peek_token_is(TokenType::Comma) {
+            self.next_token();
+            if self.peek_token_is(TokenType::RBracket) {
+                break;
+            }
+            self.next_token();
+            elements.push(self.parse_subscript()?);
+        }
+
+        Some(Expression::Tuple(TupleLiteral { span: self.span_from(token.span), token, elements }))
+    }
+
+    // Um índice ou uma fatia início:fim:passo, com as três partes opcionais
+    fn parse_subscript(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+
+        let start = if self.current_token_is(TokenType::Colon) {
+            None
+        } else {
+            let start = self.parse_expression(Precedence::Lowest)?;
+            if !self.peek_token_is(TokenType::Colon) {
+                return Some(start);
+            }
+            self.next_token();
+            Some(Box::new(start))
+        };
+
+        // O token atual é o primeiro ':'
+        let stop = self.parse_slice_part()?;
+
+        let step = if self.peek_token_is(TokenType::Colon) {
+            self.next_token();
+            self.parse_slice_part()?
+        } else {
+            None
+        };
+
+        Some(Expression::Slice(SliceExpression {
+            span: self.span_from(token.span),
+            token,
+            start,
+            stop,
+            step,
+        }))
+    }
+
+    // Parte de uma fatia depois de um ':', vazia se vier outro ':', ',' ou ']'.
+    // Devolve None em caso de erro
+    fn parse_slice_part(&mut self) -> Option<Option<Box<Expression>>> {
+        if matches!(
+            self.peek_token.token_type,
+            TokenType::Colon | TokenType::Comma | TokenType::RBracket
+        ) {
+            return Some(None);
+        }
+
+        self.next_token();
+        self.parse_expression(Precedence::Lowest).map(|part| Some(Box::new(part)))
+    }
+
+    fn parse_dict_literal(&mut self) -> Option<Expression> {
+        let token = self.current_token.clone();
+        let mut pairs = Vec::new();
+
+        while !self.peek_token_is(TokenType::RBrace) {
+            self.next_token();
+            let key = self.parse_expression(Precedence::Lowest)?;
+
+            // {x for x in xs}: compreensão de conjunto
+            if pairs.is_empty() && self.peek_token_is(TokenType::For) {
+                let clauses = self.parse_comprehension_clauses()?;
+
+                if !self.expect_peek(TokenType::RBrace) {
+                    return None;
+                }
+
+                return Some(Expression::SetComprehension(SetComprehension {
+                    span: self.span_from(token.span),
+                    token,
+                    element: Box::new(key),
+                    scope: ComprehensionScope::new(&clauses),
+                    clauses,
+                }));
+            }
+
+            if !self.expect_peek(TokenType::Colon) {
+                return None;
+            }
+
+            self.next_token();
+            let value = self.parse_expression(Precedence::Lowest)?;
+
+            // {k: v for k, v in pares}: compreensão de dicionário
+            if pairs.is_empty() && self.peek_token_is(TokenType::For) {
+                let clauses = self.parse_comprehension_clauses()?;
+
+                if !self.expect_peek(TokenType::RBrace) {
+                    return None;
+                }
+
+                return Some(Expression::DictComprehension(DictComprehension {
+                    span: self.span_from(token.span),
+                    token,
+                    key: Box::new(key),
+                    value: Box::new(value),
+                    scope: ComprehensionScope::new(&clauses),
+                    clauses,
+                }));
+            }
+
+            pairs.push((key, value));
+
+            if !self.peek_token_is(TokenType::RBrace) && !self.expect_peek(TokenType::Comma) {
+                return None;
+            }
+        }
+
+        self.next_token();
+
+        Some(Expression::Dict(DictLiteral { span: self.span_from(token.span), token, pairs }))
+    }
+
+    fn parse_attribute_expression(&mut self, object: Expression) -> Option<Expression> {
+        let token = self.current_token.clone();
+
+        if !self.expect_peek(TokenType::Identifier) {
+            return None;
+        }
+
+        let attribute = self.current_identifier();
+
+        Some(Expression::Attribute(AttributeExpression {
+            span: self.span_from(object.span()),
+            token,
+            object: Box::new(object),
+            attribute,
+        }))
+    }
+
+    fn current_precedence(&self) -> Precedence {
+        Self::token_precedence(self.current_token.token_type)
+    }
+
+    fn peek_precedence(&self) -> Precedence {
+        Self::token_precedence(self.peek_token.token_type)
+    }
+
+    fn token_precedence(token_type: TokenType) -> Precedence {
+        match token_type {
+            TokenType::Walrus => Precedence::Assignment,
+            TokenType::Or => Precedence::Or,
+            TokenType::And => Precedence::And,
+            TokenType::Eq
+            | TokenType::NotEq
+            | TokenType::Lt
+            | TokenType::Gt
+            | TokenType::LtEq
+            | TokenType::GtEq
+            | TokenType::In
+            | TokenType::Not
+            | TokenType::Is => Precedence::Comparison,
+            TokenType::Pipe => Precedence::BitwiseOr,
+            TokenType::Caret => Precedence::BitwiseXor,
+            TokenType::Ampersand => Precedence::BitwiseAnd,
+            TokenType::ShiftLeft | TokenType::ShiftRight => Precedence::Shift,
+            TokenType::Plus | TokenType::Minus => Precedence::Sum,
+            TokenType::Slash
+            | TokenType::Asterisk
+            | TokenType::FloorDiv
+            | TokenType::Percent
+            | TokenType::At => Precedence::Product,
+            TokenType::Power => Precedence::Power,
+            TokenType::LParen => Precedence::Call,
+            TokenType::LBracket => Precedence::Index,
+            TokenType::Dot => Precedence::Attribute,
+            _ => Precedence::Lowest,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::tokenize;
+    use num_bigint::BigInt;
+
+    fn parse_source(input: &str) -> Program {
+        let mut parser = Parser::new(tokenize(input).unwrap());
+        let program = parser.parse_program();
+        check_parser_errors(&parser);
+        program
+    }
+
+    #[test]
+    fn test_let_statements() {
+        let input = "
+let x = 5;
+let y = 10
+let foobar = 838383;
+";
+
+        let program = parse_source(input);
+
+        assert_eq!(program.statements.len(), 3);
+
+        let expected_identifiers = ["x", "y", "foobar"];
+
+        for (i, identifier) in expected_identifiers.iter().enumerate() {
+            match &program.statements[i] {
+                Statement::Let(let_stmt) => {
+                    assert_eq!(let_stmt.name.value, *identifier);
+                }
+                _ => panic!("Expected let statement"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_return_statements() {
+        let input = "
+return 5;
+return 10
+return
+";
+
+        let program = parse_source(input);
+
+        assert_eq!(program.statements.len(), 3);
+
+        for stmt in &program.statements {
+            match stmt {
+                Statement::Return(_) => {}
+                _ => panic!("Expected return statement"),
+            }
+        }
+
+        match &program.statements[2] {
+            Statement::Return(stmt) => assert!(stmt.return_value.is_none()),
+            _ => panic!("Expected return statement"),
+        }
+    }
+
+    #[test]
+    fn test_identifier_expression() {
+        let input = "foobar;";
+
+        let program = parse_source(input);
+
+        assert_eq!(program.statements.len(), 1);
+
+        match &program.statements[0] {
+            Statement::Expression(expr_stmt) => {
+                match &expr_stmt.expression {
+                    Some(Expression::Identifier(ident)) => {
+                        assert_eq!(ident.value, "foobar");
+                    }
+                    _ => panic!("Expression is not an identifier"),
+                }
+            }
+            _ => panic!("Statement is not an expression statement"),
+        }
+    }
+
+    #[test]
+    fn test_integer_literal_expression() {
+        let input = "5;";
+
+        let program = parse_source(input);
+
+        assert_eq!(program.statements.len(), 1);
+
+        match &program.statements[0] {
+            Statement::Expression(expr_stmt) => {
+                match &expr_stmt.expression {
+                    Some(Expression::IntegerLiteral(int_lit)) => {
+                        assert_eq!(int_lit.value, BigInt::from(5));
+                    }
+                    _ => panic!("Expression is not an integer literal"),
+                }
+            }
+            _ => panic!("Statement is not an expression statement"),
+        }
+    }
+
+    #[test]
+    fn test_big_integer_literal_expression() {
+        let input = "123456789012345678901234567890";
+
+        let program = parse_source(input);
+
+        match &program.statements[0] {
+            Statement::Expression(expr_stmt) => match &expr_stmt.expression {
+                Some(Expression::IntegerLiteral(int_lit)) => {
+                    assert_eq!(int_lit.value.to_string(), input);
+                }
+                _ => panic!("Expression is not an integer literal"),
+            },
+            _ => panic!("Statement is not an expression statement"),
+        }
+
+        // 2 ** 100 chega à AST com os operandos como BigInt; o cálculo fica para o runtime
+        match &parse_source("0x1_0000_0000_0000_0000 ** 100").statements[0] {
+            Statement::Expression(expr_stmt) => match &expr_stmt.expression {
+                Some(Expression::Infix(infix)) => {
+                    assert_eq!(infix.operator, "**");
+                    match infix.left.as_ref() {
+                        Expression::IntegerLiteral(int_lit) => assert_eq!(int_lit.value, BigInt::from(1u128 << 64)),
+                        _ => panic!("Left operand is not an integer literal"),
+                    }
+                }
+                _ => panic!("Expression is not an infix expression"),
+            },
+            _ => panic!("Statement is not an expression statement"),
+        }
+    }
+
+    #[test]
+    fn test_indented_block() {
+        let input = "
+if x:
+    a
+
+    # comentário
+    b
+c
+";
+
+        let program = parse_source(input);
+
+        assert_eq!(program.statements.len(), 2);
+
+        match &program.statements[0] {
+            Statement::If(if_stmt) => {
+                assert_eq!(if_stmt.consequence.statements.len(), 2);
+            }
+            _ => panic!("Statement is not an if statement"),
+        }
+    }
+
+    #[test]
+    fn test_nested_blocks_and_class_body() {
+        // Vários DEDENTs seguidos fecham os blocos aninhados de uma só vez
+        let input = "
+class Pilha(Base):
+    func push(self, x):
+        if x:
+            while x:
+                x -= 1
+    # comentário
+
+    func pop(self):
+        pass
+if a: b
+y = 1";
+
+        let program = parse_source(input);
+        assert_eq!(program.statements.len(), 3);
 
-        if !self.expect_peek(TokenType::LParen) {
-            return None;
+        match &program.statements[0] {
+            Statement::Class(class) => {
+                assert_eq!(class.methods.len(), 2);
+                assert_eq!(class.methods[1].name.value, "pop");
+
+                match &class.methods[0].body.statements[..] {
+                    [Statement::If(if_stmt)] => match &if_stmt.consequence.statements[..] {
+                        [Statement::While(while_stmt)] => assert_eq!(while_stmt.body.statements.len(), 1),
+                        _ => panic!("If body is not a single while statement"),
+                    },
+                    _ => panic!("Method body is not a single if statement"),
+                }
+            }
+            _ => panic!("Statement is not a class statement"),
         }
 
-        let parameters = self.parse_function_parameters();
-
-        if !self.expect_peek(TokenType::LBrace) {
-            return None;
+        // Corpo na mesma linha do ':'
+        match &program.statements[1] {
+            Statement::If(if_stmt) => assert_eq!(if_stmt.consequence.string(), "b"),
+            _ => panic!("Statement is not an if statement"),
         }
 
-        let body = self.parse_block_statement();
+        assert_eq!(program.statements[2].string(), "y = 1");
 
-        Some(Expression::FunctionLiteral(FunctionLiteral {
-            token,
-            parameters,
-            body,
-        }))
-    }
+        // Docstrings, atributos e outras instruções ficam no corpo, separados dos métodos
+        let input = "
+class Ponto:
+    \"\"\"Um ponto no plano\"\"\"
+    x = 0
+    func norma(self):
+        return self.x
+    if debug:
+        y = 1
+class Vazia: pass
+";
+
+        let program = crate::parser::parse(tokenize(input).unwrap()).unwrap();
+        assert_eq!(program.statements.len(), 2);
 
-    fn parse_function_parameters(&mut self) -> Vec<Identifier> {
-        let mut identifiers = Vec::new();
+        match &program.statements[0] {
+            Statement::Class(class) => {
+                assert_eq!(class.methods.len(), 1);
+                let body: Vec<String> = class.body.iter().map(|s| s.string()).collect();
+                assert_eq!(body, vec!["\"Um ponto no plano\"", "x = 0", "if debug:\ny = 1"]);
+            }
+            _ => panic!("Statement is not a class statement"),
+        }
 
-        if self.peek_token_is(TokenType::RParen) {
-            self.next_token();
-            return identifiers;
+        match &program.statements[1] {
+            Statement::Class(class) => {
+                assert!(class.methods.is_empty());
+                assert_eq!(class.body.len(), 1);
+                assert!(matches!(class.body[0], Statement::Pass(_)));
+            }
+            _ => panic!("Statement is not a class statement"),
         }
+    }
 
-        self.next_token();
+    #[test]
+    fn test_if_elif_else_statement() {
+        let input = "
+if x < 0:
+    sinal = -1
+elif x == 0: sinal = 0
+elif x > 100:
+    if grande:
+        sinal = 2
+else:
+    sinal = 1
+fim
+";
 
-        identifiers.push(Identifier {
-            token: self.current_token.clone(),
-            value: self.current_token.literal.clone(),
-        });
+        let program = parse_source(input);
 
-        while self.peek_token_is(TokenType::Comma) {
-            self.next_token();
-            self.next_token();
-            identifiers.push(Identifier {
-                token: self.current_token.clone(),
-                value: self.current_token.literal.clone(),
-            });
-        }
+        assert_eq!(program.statements.len(), 2);
 
-        if !self.expect_peek(TokenType::RParen) {
-            return Vec::new();
+        match &program.statements[0] {
+            Statement::If(if_stmt) => {
+                assert_eq!(if_stmt.condition.string(), "(x < 0)");
+                assert_eq!(if_stmt.elif_branches.len(), 2);
+                assert_eq!(if_stmt.elif_branches[0].1.string(), "sinal = 0");
+                assert!(matches!(if_stmt.elif_branches[1].1.statements[0], Statement::If(_)));
+                assert_eq!(if_stmt.alternative.as_ref().map(|b| b.string()), Some(String::from("sinal = 1")));
+                assert_eq!(&input[if_stmt.span.start..if_stmt.span.end], input.trim().strip_suffix("\nfim").unwrap());
+            }
+            _ => panic!("Statement is not an if statement"),
         }
-
-        identifiers
     }
 
-    fn parse_call_expression(&mut self, function: Expression) -> Option<Expression> {
-        Some(Expression::CallExpression(CallExpression {
-            token: self.current_token.clone(),
-            function: Box::new(function),
-            arguments: self.parse_expression_list(TokenType::RParen),
-        }))
-    }
+    #[test]
+    fn test_while_function_and_pass_statements() {
+        let input = "
+func soma(a, b) -> int:
+    while a > 0:
+        pass
+    return a + b
 
-    fn parse_array_literal(&mut self) -> Option<Expression> {
-        Some(Expression::ArrayLiteral(ArrayLiteral {
-            token: self.current_token.clone(),
-            elements: self.parse_expression_list(TokenType::RBracket),
-        }))
-    }
+dobro = func(x): return x * 2
+";
 
-    fn parse_expression_list(&mut self, end: TokenType) -> Vec<Expression> {
-        let mut list = Vec::new();
+        let program = parse_source(input);
 
-        if self.peek_token_is(end) {
-            self.next_token();
-            return list;
+        assert_eq!(program.statements.len(), 2);
+
+        match &program.statements[0] {
+            Statement::Function(func) => {
+                assert_eq!(func.name.value, "soma");
+                assert_eq!(func.parameters.len(), 2);
+                assert!(matches!(func.body.statements[0], Statement::While(_)));
+                assert!(matches!(func.body.statements[1], Statement::Return(_)));
+                assert_eq!(func.body.statements[0].string(), "while (a > 0):\npass\n");
+            }
+            _ => panic!("Statement is not a function statement"),
         }
 
-        self.next_token();
-        if let Some(expr) = self.parse_expression(Precedence::Lowest) {
-            list.push(expr);
+        match &program.statements[1] {
+            Statement::Expression(stmt) => match &stmt.expression {
+                Some(Expression::Assignment(assign)) => {
+                    assert!(matches!(assign.value.as_ref(), Expression::FunctionLiteral(_)));
+                }
+                _ => panic!("Expression is not an assignment"),
+            },
+            _ => panic!("Statement is not an expression statement"),
         }
+    }
 
-        while self.peek_token_is(TokenType::Comma) {
-            self.next_token();
-            self.next_token();
-            if let Some(expr) = self.parse_expression(Precedence::Lowest) {
-                list.push(expr);
+    #[test]
+    fn test_parameters_and_arguments() {
+        let input = "func f(a, b=1, *args, c, d=2, **kwargs):\n    pass\n";
+        match &parse_source(input).statements[0] {
+            Statement::Function(func) => {
+                let kinds: Vec<ParameterKind> = func.parameters.iter().map(|p| p.kind).collect();
+                assert_eq!(kinds, vec![
+                    ParameterKind::Positional,
+                    ParameterKind::Positional,
+                    ParameterKind::VarPositional,
+                    ParameterKind::KeywordOnly,
+                    ParameterKind::KeywordOnly,
+                    ParameterKind::VarKeyword,
+                ]);
+                assert_eq!(func.parameters[1].default.as_ref().map(|d| d.string()), Some(String::from("1")));
+                assert_eq!(&input[func.parameters[2].span.start..func.parameters[2].span.end], "*args");
             }
+            _ => panic!("Statement is not a function statement"),
         }
 
-        if !self.expect_peek(end) {
-            return Vec::new();
+        let tests = vec![
+            ("func(a, *, b): pass", "func(a, *, b) pass\n"),
+            ("lambda x, y=2, *r, **k: x", "lambda x, y=2, *r, **k: x"),
+            ("lambda: 0", "lambda : 0"),
+            ("f(1, c=2, *xs, **opts)", "f(1, c=2, *xs, **opts)"),
+            ("f(*a, *b, k=1, *c)", "f(*a, *b, k=1, *c)"),
+            ("f(x == 1, y := 2)", "f((x == 1), (y := 2))"),
+        ];
+
+        for (input, expected) in tests {
+            let program = parse_source(input);
+            assert_eq!(program.string(), expected, "input: {}", input);
         }
 
-        list
+        let input = "f(1, c=2, *xs, **opts)";
+        match &parse_source(input).statements[0] {
+            Statement::Expression(stmt) => match &stmt.expression {
+                Some(Expression::Call(call)) => {
+                    assert!(matches!(call.arguments[0].kind, ArgumentKind::Positional));
+                    assert!(matches!(&call.arguments[1].kind, ArgumentKind::Keyword(name) if name.value == "c"));
+                    assert!(matches!(call.arguments[2].kind, ArgumentKind::Unpack));
+                    assert!(matches!(call.arguments[3].kind, ArgumentKind::UnpackKeywords));
+                    assert_eq!(&input[call.arguments[3].span.start..call.arguments[3].span.end], "**opts");
+                }
+                _ => panic!("Expression is not a call expression"),
+            },
+            _ => panic!("Statement is not an expression statement"),
+        }
     }
 
-    fn parse_index_expression(&mut self, left: Expression) -> Option<Expression> {
-        let token = self.current_token.clone();
-        
-        self.next_token();
-        let index = match self.parse_expression(Precedence::Lowest) {
-            Some(expr) => expr,
-            None => return None,
-        };
-
-        if !self.expect_peek(TokenType::RBracket) {
-            return None;
+    #[test]
+    fn test_parameter_and_argument_errors() {
+        let tests = vec![
+            ("func f(a, a): pass\n", "duplicate argument 'a' in function definition at line 1 column 11"),
+            ("func f(a=1, b): pass\n", "non-default argument follows default argument at line 1 column 13"),
+            ("func f(**k, a): pass\n", "arguments cannot follow var-keyword argument at line 1 column 13"),
+            ("func f(*a, *b): pass\n", "* argument may appear only once at line 1 column 12"),
+            ("func f(a, *): pass\n", "named arguments must follow bare * at line 1 column 11"),
+            ("func f(*, **k): pass\n", "named arguments must follow bare * at line 1 column 8"),
+            ("lambda *a=1: a\n", "var-positional and var-keyword arguments cannot have default value at line 1 column 9"),
+            ("f(a=1, 2)\n", "positional argument follows keyword argument at line 1 column 8"),
+            ("f(**k, 2)\n", "positional argument follows keyword argument unpacking at line 1 column 8"),
+            ("f(**k, *xs)\n", "iterable argument unpacking follows keyword argument unpacking at line 1 column 8"),
+            ("f(a=1, a=2)\n", "keyword argument repeated: a at line 1 column 8"),
+        ];
+
+        for (input, expected) in tests {
+            let errors = crate::parser::parse(tokenize(input).unwrap()).unwrap_err();
+            assert_eq!(errors, expected, "input: {}", input);
         }
-
-        Some(Expression::IndexExpression(IndexExpression {
-            token,
-            left: Box::new(left),
-            index: Box::new(index),
-        }))
     }
 
-    fn parse_hash_literal(&mut self) -> Option<Expression> {
-        let token = self.current_token.clone();
-        let mut pairs = HashMap::new();
+    #[test]
+    fn test_for_statement() {
+        let input = "
+for i in range(10):
+    total += i
+for chave, valor in d.items():
+    pass
+for (i, (a, b)), in pares:
+    pass
+for self.atual in fila:
+    pass
+else:
+    vazio()
+for x in 1, 2:
+    pass
+";
+
+        let program = parse_source(input);
+
+        let loops: Vec<&ForStatement> = program
+            .statements
+            .iter()
+            .map(|stmt| match stmt {
+                Statement::For(for_stmt) => for_stmt,
+                _ => panic!("Statement is not a for statement"),
+            })
+            .collect();
+
+        assert_eq!(loops.len(), 5);
+        assert_eq!(loops[0].iterator.string(), "i");
+        assert_eq!(loops[0].iterable.string(), "range(10)");
+        assert_eq!(loops[1].iterator.string(), "(chave, valor)");
+        assert_eq!(loops[1].iterable.string(), "d.items()");
+        assert_eq!(loops[2].iterator.string(), "((i, (a, b)),)");
+        assert_eq!(loops[3].iterator.string(), "self.atual");
+        assert_eq!(loops[4].iterable.string(), "(1, 2)");
+        assert!(loops[2].alternative.is_none());
+        assert_eq!(loops[3].alternative.as_ref().map(|b| b.string()), Some(String::from("vazio()")));
+
+        let source = |node: &dyn Node| &input[node.span().start..node.span().end];
+        assert_eq!(source(loops[1].iterator.as_ref()), "chave, valor");
+        assert_eq!(source(loops[2].iterator.as_ref()), "(i, (a, b)),");
+        assert_eq!(source(loops[4].iterable.as_ref()), "1, 2");
+        assert_eq!(source(loops[3]), "for self.atual in fila:\n    pass\nelse:\n    vazio()");
+
+        let errors = crate::parser::parse(tokenize("for a, f(b) in xs:\n    pass\n").unwrap()).unwrap_err();
+        assert!(errors.starts_with("cannot assign to f(b) at line 1 column 8"));
+    }
 
-        while !self.peek_token_is(TokenType::RBrace) {
-            self.next_token();
-            let key = match self.parse_expression(Precedence::Lowest) {
-                Some(expr) => expr,
-                None => return None,
-            };
+    #[test]
+    fn test_import_statements() {
+        let tests = vec![
+            ("import os", "import os\n"),
+            ("import os.path as caminho, sys", "import os.path as caminho, sys\n"),
+            ("from pkg.mod import a as b, c", "from pkg.mod import a as b, c\n"),
+            ("from ..util import (x,\n    y as z,\n)", "from ..util import x, y as z\n"),
+            ("from . import irmao", "from . import irmao\n"),
+            ("from math import *", "from math import *\n"),
+        ];
+
+        for (input, expected) in tests {
+            let program = parse_source(input);
+            assert_eq!(program.string(), expected, "input: {}", input);
+        }
 
-            if !self.expect_peek(TokenType::Colon) {
-                return None;
+        match &parse_source("import os.path as caminho, sys").statements[0] {
+            Statement::Import(import) => {
+                assert!(!import.is_from);
+                assert_eq!(import.module, "os.path");
+                assert_eq!(import.items[1], (String::from("sys"), None));
             }
+            _ => panic!("Statement is not an import statement"),
+        }
+    }
 
-            self.next_token();
-            let value = match self.parse_expression(Precedence::Lowest) {
-                Some(expr) => expr,
-                None => return None,
-            };
-
-            pairs.insert(key, value);
+    #[test]
+    fn test_with_statement() {
+        let input = "with open(p) as f, lock:\n    dados = f.read()\n";
+        let program = parse_source(input);
 
-            if !self.peek_token_is(TokenType::RBrace) && !self.expect_peek(TokenType::Comma) {
-                return None;
+        match &program.statements[0] {
+            Statement::With(with) => {
+                assert_eq!(with.items.len(), 2);
+                assert_eq!(with.items[0].0.string(), "open(p)");
+                assert_eq!(with.items[0].1.as_ref().map(|t| t.string()), Some(String::from("f")));
+                assert!(with.items[1].1.is_none());
+                assert_eq!(with.body.statements.len(), 1);
+                assert_eq!(&input[with.span.start..with.span.end], input.trim_end());
             }
+            _ => panic!("Statement is not a with statement"),
         }
+    }
 
-        if !self.expect_peek(TokenType::RBrace) {
-            return None;
+    #[test]
+    fn test_raise_yield_break_and_continue() {
+        let input = "
+func gerador(xs):
+    for x in xs:
+        if x is None:
+            continue
+        elif x < 0:
+            break
+        recebido = yield x
+    yield from outros()
+    yield
+    raise ValueError(\"fim\") from causa
+raise
+";
+
+        let program = parse_source(input);
+
+        assert_eq!(program.statements.len(), 2);
+        assert_eq!(program.statements[1].string(), "raise\n");
+
+        match &program.statements[0] {
+            Statement::Function(func) => {
+                let body: Vec<String> = func.body.statements.iter().map(|s| s.string()).collect();
+                assert_eq!(
+                    body[0],
+                    "for x in xs:\nif (x is None):\ncontinue\nelif (x < 0):\nbreak\nrecebido = (yield x)"
+                );
+                assert_eq!(body[1], "(yield from outros())");
+                assert_eq!(body[2], "(yield)");
+                assert_eq!(body[3], "raise ValueError(\"fim\") from causa\n");
+            }
+            _ => panic!("Statement is not a function statement"),
         }
 
-        Some(Expression::HashLiteral(HashLiteral { token, pairs }))
+        let errors = crate::parser::parse(tokenize("while x:\n    f = func(): break\ncontinue\n").unwrap()).unwrap_err();
+        let errors: Vec<&str> = errors.lines().collect();
+        assert_eq!(errors, vec!["'break' outside loop at line 2 column 17", "'continue' outside loop at line 3 column 1"]);
     }
 
-    fn parse_property_access_expression(&mut self, object: Expression) -> Option<Expression> {
-        let token = self.current_token.clone();
-        
-        if !self.expect_peek(TokenType::Identifier) {
-            return None;
+    #[test]
+    fn test_try_statement() {
+        let input = "
+try:
+    abrir(caminho)
+except ValueError as erro:
+    log(erro)
+except Erros.Leitura:
+    pass
+except:
+    raise_()
+finally:
+    fechar()
+";
+
+        let program = parse_source(input);
+
+        assert_eq!(program.statements.len(), 1);
+
+        match &program.statements[0] {
+            Statement::Try(try_stmt) => {
+                let clauses: Vec<(Option<String>, Option<String>)> = try_stmt
+                    .except_blocks
+                    .iter()
+                    .map(|e| (e.exception.as_ref().map(|x| x.string()), e.name.as_ref().map(|n| n.value.clone())))
+                    .collect();
+                assert_eq!(
+                    clauses,
+                    vec![
+                        (Some(String::from("ValueError")), Some(String::from("erro"))),
+                        (Some(String::from("Erros.Leitura")), None),
+                        (None, None),
+                    ]
+                );
+                assert_eq!(try_stmt.finally_block.as_ref().map(|b| b.string()), Some(String::from("fechar()")));
+            }
+            _ => panic!("Statement is not a try statement"),
         }
-        
-        let property = Identifier {
-            token: self.current_token.clone(),
-            value: self.current_token.literal.clone(),
-        };
 
-        Some(Expression::PropertyAccess(PropertyAccessExpression {
-            token,
-            object: Box::new(object),
-            property,
-        }))
+        let errors = crate::parser::parse(tokenize("try:\n    x\ny\n").unwrap()).unwrap_err();
+        assert!(errors.starts_with("expected except or finally after try block at line 3 column 1"));
     }
 
-    fn current_precedence(&self) -> Precedence {
-        Self::token_precedence(&self.current_token.token_type)
+    #[test]
+    fn test_fstring_literal() {
+        let input = r#"f"Hello {name!r}, total={a + b:.2f}""#;
+
+        let program = parse_source(input);
+
+        assert_eq!(program.statements.len(), 1);
+        assert_eq!(
+            program.string(),
+            r#"f"Hello {name!r}, total={(a + b):.2f}""#
+        );
+
+        let tests = vec![
+            (r#"f"{a:{w}}""#, r#"f"{a:{w}}""#),
+            (r#"f"{v!r:>{width}.{p}f}""#, r#"f"{v!r:>{width}.{p}f}""#),
+            (r#"f"x: {x=}""#, r#"f"x: x={x!r}""#),
+            (r#"f"{x + 1 = :.2f}""#, r#"f"x + 1 = {(x + 1):.2f}""#),
+            (r#"f"{a == b}""#, r#"f"{(a == b)}""#),
+            // ':' dentro de colchetes ou chaves pertence à expressão
+            (r#"f"{xs[1:2]}""#, r#"f"{(xs[1:2])}""#),
+            (r#"f"{ {'a': 1}['a'] }""#, r#"f"{({"a": 1}["a"])}""#),
+        ];
+
+        for (input, expected) in tests {
+            assert_eq!(parse_source(input).string(), expected, "input: {}", input);
+            assert_eq!(parse_source(expected).string(), expected, "input: {}", expected);
+        }
     }
 
-    fn peek_precedence(&self) -> Precedence {
-        Self::token_precedence(&self.peek_token.token_type)
+    #[test]
+    fn test_bitwise_and_assignment_precedence() {
+        let tests = vec![
+            ("a | b ^ c & d", "(a | (b ^ (c & d)))"),
+            ("a << 1 + b", "(a << (1 + b))"),
+            ("a // b @ c", "((a // b) @ c)"),
+            ("~a & b", "((~a) & b)"),
+            ("x = y = a | b", "x = y = (a | b)"),
+            ("x <<= a | b", "x <<= (a | b)"),
+            ("(n := a >> 2)", "(n := (a >> 2))"),
+        ];
+
+        for (input, expected) in tests {
+            let program = parse_source(input);
+            assert_eq!(program.string(), expected);
+        }
     }
 
-    fn token_precedence(token_type: &TokenType) -> Precedence {
-        match token_type {
-            TokenType::Eq | TokenType::NotEq => Precedence::Equals,
-            TokenType::Lt | TokenType::Gt => Precedence::LessGreater,
-            TokenType::Plus | TokenType::Minus => Precedence::Sum,
-            TokenType::Slash | TokenType::Asterisk => Precedence::Product,
-            TokenType::LParen => Precedence::Call,
-            TokenType::LBracket => Precedence::Index,
-            TokenType::Dot => Precedence::Member,
-            _ => Precedence::Lowest,
+    #[test]
+    fn test_operator_precedence() {
+        let tests = vec![
+            ("a or b and c", "(a or (b and c))"),
+            ("a and not b or c", "((a and (not b)) or c)"),
+            ("not a == b", "(not (a == b))"),
+            ("not not a", "(not (not a))"),
+            ("a + b <= c * d", "((a + b) <= (c * d))"),
+            ("a >= b and c < d", "((a >= b) and (c < d))"),
+            ("a | b == c", "((a | b) == c)"),
+            ("a % b * c", "((a % b) * c)"),
+            ("a - b % c", "(a - (b % c))"),
+            ("2 ** 3 ** 2", "(2 ** (3 ** 2))"),
+            ("-2 ** 2", "(-(2 ** 2))"),
+            ("2 ** -1", "(2 ** (-1))"),
+            ("-a.b ** 2", "(-(a.b ** 2))"),
+            ("a * -b ** c", "(a * (-(b ** c)))"),
+            ("+a - -b", "((+a) - (-b))"),
+            ("~a ** b", "(~(a ** b))"),
+            ("f(x) ** 2 // 3", "((f(x) ** 2) // 3)"),
+            ("x = a or b", "x = (a or b)"),
+            ("x += y ** 2 % 3", "x += ((y ** 2) % 3)"),
+            ("(n := not a)", "(n := (not a))"),
+        ];
+
+        for (input, expected) in tests {
+            let program = parse_source(input);
+            assert_eq!(program.string(), expected, "input: {}", input);
         }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::lexer::lexer::Lexer;
+    #[test]
+    fn test_comparison_operators() {
+        let tests = vec![
+            ("x in items", "(x in items)"),
+            ("x not in seen", "(x not in seen)"),
+            ("a is None", "(a is None)"),
+            ("a is not b", "(a is not b)"),
+            ("not a in b", "(not (a in b))"),
+            ("a is not not b", "(a is not (not b))"),
+            ("0 <= x < 10", "(0 <= x < 10)"),
+            ("a < b == c != d", "(a < b == c != d)"),
+            ("a + 1 < b * 2 <= c", "((a + 1) < (b * 2) <= c)"),
+            ("a in b not in c is d", "(a in b not in c is d)"),
+            ("a in b and c is not None", "((a in b) and (c is not None))"),
+            ("x == (a < b)", "(x == (a < b))"),
+        ];
+
+        for (input, expected) in tests {
+            let program = parse_source(input);
+            assert_eq!(program.string(), expected, "input: {}", input);
+        }
+    }
 
     #[test]
-    fn test_let_statements() {
-        let input = "
-            let x = 5;
-            let y = 10;
-            let foobar = 838383;
-        ";
+    fn test_chained_comparison() {
+        let input = "0 <= x < 10";
+        let program = parse_source(input);
 
-        let lexer = Lexer::new(input.to_string());
-        let mut parser = Parser::new(lexer.tokenize());
-        let program = parser.parse_program();
+        match &program.statements[0] {
+            Statement::Expression(stmt) => match &stmt.expression {
+                Some(Expression::Comparison(cmp)) => {
+                    assert_eq!(cmp.left.string(), "0");
+                    let operators: Vec<&str> = cmp.comparisons.iter().map(|(op, _)| op.as_str()).collect();
+                    assert_eq!(operators, vec!["<=", "<"]);
+                    assert_eq!(cmp.comparisons[1].1.string(), "10");
+                    assert_eq!(&input[cmp.span.start..cmp.span.end], input);
+                }
+                _ => panic!("Expression is not a comparison expression"),
+            },
+            _ => panic!("Statement is not an expression statement"),
+        }
 
-        check_parser_errors(&parser);
+        let errors = crate::parser::parse(tokenize("a not b").unwrap()).unwrap_err();
+        assert!(errors.starts_with("expected next token to be In, got Identifier instead at line 1 column 7"));
+    }
 
-        assert_eq!(program.statements.len(), 3);
+    #[test]
+    fn test_comprehensions() {
+        let tests = vec![
+            ("[x * 2 for x in xs if x > 0]", "[(x * 2) for x in xs if (x > 0)]"),
+            ("{k: v for k, v in pairs}", "{k: v for (k, v) in pairs}"),
+            ("{x for x in xs}", "{x for x in xs}"),
+            ("sum(x for x in xs)", "sum((x for x in xs))"),
+            ("(x for x in xs if x if not y)", "(x for x in xs if x if (not y))"),
+            ("[i * j for i in a for j in b if i != j]", "[(i * j) for i in a for j in b if (i != j)]"),
+            ("[[y for y in row] for row in m]", "[[y for y in row] for row in m]"),
+            ("[x for x in [1, 2]]", "[x for x in [1, 2]]"),
+        ];
+
+        for (input, expected) in tests {
+            let program = parse_source(input);
+            assert_eq!(program.string(), expected, "input: {}", input);
+            assert_eq!(parse_source(expected).string(), expected, "input: {}", expected);
+        }
 
-        let expected_identifiers = vec!["x", "y", "foobar"];
+        let input = "[x for x in xs if x for y in x]";
+        match &parse_source(input).statements[0] {
+            Statement::Expression(stmt) => match &stmt.expression {
+                Some(Expression::ListComprehension(comp)) => {
+                    assert_eq!(comp.clauses.len(), 2);
+                    assert_eq!(comp.clauses[0].conditions.len(), 1);
+                    assert_eq!(&input[comp.clauses[0].span.start..comp.clauses[0].span.end], "for x in xs if x");
+                    assert_eq!(comp.span.end, input.len());
+
+                    // `x` e `y` são locais; o `xs` da primeira cláusula vem de fora
+                    let locals: Vec<&str> = comp.scope.locals.iter().map(|l| l.value.as_str()).collect();
+                    assert_eq!(locals, vec!["x", "y"]);
+                    assert!(!comp.scope.is_local("xs"));
+                }
+                _ => panic!("Expression is not a list comprehension"),
+            },
+            _ => panic!("Statement is not an expression statement"),
+        }
 
-        for (i, identifier) in expected_identifiers.iter().enumerate() {
-            match &program.statements[i] {
-                Statement::Let(let_stmt) => {
-                    assert_eq!(let_stmt.name.value, *identifier);
+        // Atributos como alvo não ligam nomes no escopo da compreensão
+        match &parse_source("{k: v for (k, *v), self.n in pairs}").statements[0] {
+            Statement::Expression(stmt) => match &stmt.expression {
+                Some(Expression::DictComprehension(comp)) => {
+                    let locals: Vec<&str> = comp.scope.locals.iter().map(|l| l.value.as_str()).collect();
+                    assert_eq!(locals, vec!["k", "v"]);
+                    assert!(!comp.scope.is_local("self"));
                 }
-                _ => panic!("Expected let statement"),
-            }
+                _ => panic!("Expression is not a dict comprehension"),
+            },
+            _ => panic!("Statement is not an expression statement"),
         }
+
+        // Um gerador sem parênteses próprios precisa ser o único argumento
+        let errors = crate::parser::parse(tokenize("f(x for x in xs, y)\n").unwrap()).unwrap_err();
+        assert!(errors.contains("expected next token to be RParen, got Comma instead at line 1 column 16"));
     }
 
     #[test]
-    fn test_return_statements() {
-        let input = "
-            return 5;
-            return 10;
-            return 993322;
-        ";
+    fn test_slices_and_subscripts() {
+        let tests = vec![
+            ("xs[1]", "(xs[1])"),
+            ("xs[1:3]", "(xs[1:3])"),
+            ("xs[:n]", "(xs[:n])"),
+            ("xs[n:]", "(xs[n:])"),
+            ("xs[:]", "(xs[:])"),
+            ("xs[::-1]", "(xs[::(-1)])"),
+            ("xs[a + 1:b * 2:2]", "(xs[(a + 1):(b * 2):2])"),
+            ("m[i, j]", "(m[i, j])"),
+            ("m[i,]", "(m[(i,)])"),
+            ("m[1:, ::2, k]", "(m[1:, ::2, k])"),
+            ("xs[1:3][0]", "((xs[1:3])[0])"),
+            ("xs[1:2] = ys", "(xs[1:2]) = ys"),
+        ];
+
+        for (input, expected) in tests {
+            let program = parse_source(input);
+            assert_eq!(program.string(), expected, "input: {}", input);
+
+            // A saída de `string` pode ser analisada de novo
+            assert_eq!(parse_source(expected).string(), expected, "input: {}", expected);
+        }
 
-        let lexer = Lexer::new(input.to_string());
-        let mut parser = Parser::new(lexer.tokenize());
-        let program = parser.parse_program();
+        let input = "xs[::-1]";
+        match &parse_source(input).statements[0] {
+            Statement::Expression(stmt) => match &stmt.expression {
+                Some(Expression::Index(index)) => match index.index.as_ref() {
+                    Expression::Slice(slice) => {
+                        assert!(slice.start.is_none() && slice.stop.is_none());
+                        assert_eq!(slice.step.as_ref().map(|s| s.string()), Some(String::from("(-1)")));
+                        assert_eq!(&input[slice.span.start..slice.span.end], "::-1");
+                    }
+                    _ => panic!("Index is not a slice"),
+                },
+                _ => panic!("Expression is not an index expression"),
+            },
+            _ => panic!("Statement is not an expression statement"),
+        }
+    }
 
-        check_parser_errors(&parser);
+    #[test]
+    fn test_tuples_and_unpacking() {
+        let tests = vec![
+            ("a, b = b, a", "(a, b) = (b, a)"),
+            ("first, *rest = items", "(first, *rest) = items"),
+            ("(x, y) = point", "(x, y) = point"),
+            ("x, = xs", "(x,) = xs"),
+            ("a, (b, *c) = d", "(a, (b, *c)) = d"),
+            ("a = b = 1, 2", "a = b = (1, 2)"),
+            ("t = 1, 2,", "t = (1, 2)"),
+            ("t = (1,)", "t = (1,)"),
+            ("t = ()", "t = ()"),
+            ("t = (1)", "t = 1"),
+            ("f((1, 2), 3)", "f((1, 2), 3)"),
+            ("[*a, *b]", "[*a, *b]"),
+            ("x = *a, b", "x = (*a, b)"),
+            ("t = 1, *rest", "t = (1, *rest)"),
+            ("t = (*a,)", "t = (*a,)"),
+            ("[a, *b] = xs", "[a, *b] = xs"),
+            ("f(*a, *b)", "f(*a, *b)"),
+            ("n = (y := 2)", "n = (y := 2)"),
+        ];
+
+        for (input, expected) in tests {
+            let program = parse_source(input);
+            assert_eq!(program.string(), expected, "input: {}", input);
+            assert_eq!(parse_source(expected).string(), expected, "input: {}", expected);
+        }
 
-        assert_eq!(program.statements.len(), 3);
+        let input = "first, *rest = items\nreturn a, b\nfor k, *v in pares: pass\n";
+        let program = parse_source(input);
 
-        for stmt in program.statements {
-            match stmt {
-                Statement::Return(_) => {}
-                _ => panic!("Expected return statement"),
+        match &program.statements[0] {
+            Statement::Expression(stmt) => match &stmt.expression {
+                Some(Expression::Assignment(assign)) => match assign.left.as_ref() {
+                    Expression::Tuple(tuple) => {
+                        assert!(matches!(&tuple.elements[1], Expression::Starred(s) if s.value.string() == "rest"));
+                        assert_eq!(&input[tuple.span.start..tuple.span.end], "first, *rest");
+                    }
+                    _ => panic!("Assignment target is not a tuple"),
+                },
+                _ => panic!("Expression is not an assignment"),
+            },
+            _ => panic!("Statement is not an expression statement"),
+        }
+
+        match &program.statements[1] {
+            Statement::Return(ret) => {
+                assert!(matches!(&ret.return_value, Some(Expression::Tuple(t)) if t.elements.len() == 2));
             }
+            _ => panic!("Statement is not a return statement"),
+        }
+
+        match &program.statements[2] {
+            Statement::For(stmt) => assert_eq!(stmt.iterator.string(), "(k, *v)"),
+            _ => panic!("Statement is not a for statement"),
         }
     }
 
     #[test]
-    fn test_identifier_expression() {
-        let input = "foobar;";
+    fn test_logical_expressions() {
+        let program = parse_source("a or b and c");
 
-        let lexer = Lexer::new(input.to_string());
-        let mut parser = Parser::new(lexer.tokenize());
-        let program = parser.parse_program();
+        match &program.statements[0] {
+            Statement::Expression(stmt) => match &stmt.expression {
+                Some(Expression::Logical(or)) => {
+                    assert_eq!(or.operator, "or");
+                    assert!(matches!(or.right.as_ref(), Expression::Logical(and) if and.operator == "and"));
+                }
+                _ => panic!("Expression is not a logical expression"),
+            },
+            _ => panic!("Statement is not an expression statement"),
+        }
+    }
 
-        check_parser_errors(&parser);
+    #[test]
+    fn test_invalid_assignment_targets() {
+        let tests = vec![
+            ("a + b = c", "cannot assign to (a + b) at line 1 column 7"),
+            ("a or b = c", "cannot assign to (a or b) at line 1 column 8"),
+            ("f() += 1", "cannot assign to f() at line 1 column 5"),
+            ("(x.y := 1)", "cannot use := with a target that is not an identifier at line 1 column 6"),
+            ("n := 1", "cannot use := without parentheses here at line 1 column 3"),
+            ("x = y := 2", "cannot use := without parentheses here at line 1 column 7"),
+            ("x = 1, y := 2", "cannot use := without parentheses here at line 1 column 10"),
+            // `=` e os operadores aumentados não são operadores de expressão
+            ("if x = 1:\n    pass", "expected next token to be Colon, got Assign instead at line 1 column 6"),
+            ("y = [x = 1]", "expected next token to be Comma, got Assign instead at line 1 column 8"),
+            ("f(a.b=1)", "expression cannot contain assignment, perhaps you meant \"==\"? at line 1 column 6"),
+            ("x = y += 1", "no prefix parse function for PlusAssign found at line 1 column 7"),
+            ("a, 1 = xs", "cannot assign to 1 at line 1 column 6"),
+            ("a, b += 1", "cannot assign to (a, b) at line 1 column 6"),
+            ("*a = xs", "starred assignment target must be in a list or tuple at line 1 column 4"),
+            ("*a, *b = xs", "multiple starred expressions in assignment at line 1 column 8"),
+            ("for *a in xs: pass", "starred assignment target must be in a list or tuple at line 1 column 5"),
+            ("for *a, *b in xs: pass", "multiple starred expressions in assignment at line 1 column 5"),
+            ("[a, f()] = xs", "cannot assign to f() at line 1 column 10"),
+            ("[*a, *b] = xs", "multiple starred expressions in assignment at line 1 column 10"),
+            ("[a, b] += xs", "cannot assign to [a, b] at line 1 column 8"),
+            // *valor só é aceito em tuplas, listas e argumentos
+            ("x = *a", "cannot use starred expression here at line 1 column 5"),
+            ("*a", "cannot use starred expression here at line 1 column 1"),
+            ("y = (*a)", "cannot use starred expression here at line 1 column 6"),
+            ("for x in *a: pass", "cannot use starred expression here at line 1 column 10"),
+            ("return *a", "cannot use starred expression here at line 1 column 8"),
+            ("[*a for a in b]", "cannot use starred expression here at line 1 column 2"),
+            ("xs[*a]", "no prefix parse function for Asterisk found at line 1 column 4"),
+        ];
+
+        for (input, expected) in tests {
+            let errors = crate::parser::parse(tokenize(input).unwrap()).unwrap_err();
+            assert!(errors.starts_with(expected), "input: {}, errors: {}", input, errors);
+        }
 
-        assert_eq!(program.statements.len(), 1);
+        // O restante do statement é descartado e a linha seguinte é analisada normalmente
+        let (program, errors) = crate::parser::parse_from_source("x := f(1) + 2\ny = 3\n");
+        assert_eq!(errors, vec!["cannot use := without parentheses here at line 1 column 3"]);
+        assert_eq!(program.string(), "y = 3");
+    }
+
+    #[test]
+    fn test_node_spans() {
+        let input = "total = preço * 2\nif total:\n    f(total)\n";
+
+        let program = parse_source(input);
+
+        let source = |node: &dyn Node| &input[node.span().start..node.span().end];
+
+        assert_eq!(source(&program.statements[0]), "total = preço * 2");
+        assert_eq!(source(&program.statements[1]), "if total:\n    f(total)");
 
         match &program.statements[0] {
-            Statement::Expression(expr_stmt) => {
-                match &expr_stmt.expression {
-                    Expression::Identifier(ident) => {
-                        assert_eq!(ident.value, "foobar");
-                    }
-                    _ => panic!("Expression is not an identifier"),
+            Statement::Expression(stmt) => match &stmt.expression {
+                Some(Expression::Assignment(assign)) => {
+                    assert_eq!(source(assign.value.as_ref()), "preço * 2");
                 }
-            }
+                _ => panic!("Expression is not an assignment"),
+            },
             _ => panic!("Statement is not an expression statement"),
         }
     }
 
     #[test]
-    fn test_integer_literal_expression() {
-        let input = "5;";
+    fn test_odin_source() {
+        let input = "
+class Contador(Base):
+    func __init__(self, inicio):
+        self.valor = inicio
 
-        let lexer = Lexer::new(input.to_string());
-        let mut parser = Parser::new(lexer.tokenize());
-        let program = parser.parse_program();
+    func incrementa(self) -> int:
+        self.valor += 1
+        return self.valor
 
-        check_parser_errors(&parser);
+for valor in [1, 2, 3,]:
+    total = {'a': valor, 'b': None}[chave]
 
-        assert_eq!(program.statements.len(), 1);
+dobro = lambda x: x * 2
+";
+
+        let program = crate::parser::parse(tokenize(input).unwrap()).unwrap();
+
+        assert_eq!(program.statements.len(), 3);
 
         match &program.statements[0] {
-            Statement::Expression(expr_stmt) => {
-                match &expr_stmt.expression {
-                    Expression::IntegerLiteral(int_lit) => {
-                        assert_eq!(int_lit.value, 5);
-                    }
-                    _ => panic!("Expression is not an integer literal"),
-                }
+            Statement::Class(class) => {
+                assert_eq!(class.name.value, "Contador");
+                assert_eq!(class.parent.as_ref().map(|p| p.value.as_str()), Some("Base"));
+
+                let names: Vec<&str> = class.methods.iter().map(|m| m.name.value.as_str()).collect();
+                assert_eq!(names, vec!["__init__", "incrementa"]);
+                assert_eq!(class.methods[1].return_type.as_ref().map(|t| t.string()), Some(String::from("int")));
+                assert_eq!(class.methods[1].body.statements.len(), 2);
             }
-            _ => panic!("Statement is not an expression statement"),
+            _ => panic!("Statement is not a class statement"),
+        }
+
+        match &program.statements[1] {
+            Statement::For(for_stmt) => {
+                assert_eq!(for_stmt.iterator.string(), "valor");
+                assert_eq!(for_stmt.iterable.string(), "[1, 2, 3]");
+                assert_eq!(for_stmt.body.string(), "total = ({\"a\": valor, \"b\": None}[chave])");
+            }
+            _ => panic!("Statement is not a for statement"),
         }
+
+        assert_eq!(program.statements[2].string(), "dobro = lambda x: (x * 2)");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let errors = crate::parser::parse(tokenize("let = 5\nf(1 2)\n").unwrap()).unwrap_err();
+
+        assert!(errors.contains("expected next token to be Identifier, got Assign instead at line 1 column 5"));
+        assert!(errors.contains("expected next token to be Comma, got Int instead at line 2 column 5"));
     }
 
     fn check_parser_errors(parser: &Parser) {
@@ -852,4 +3178,4 @@ mod tests {
         }
         panic!("Parser had errors");
     }
-}
\ No newline at end of file
+}